@@ -0,0 +1,117 @@
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+use crate::api::Api;
+use crate::Args;
+
+/// Accounts created within this long are counted as recent.
+const RECENT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// How the members of a group break down, from a sample of the newest ones.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub sampled: u32,
+    /// Accounts created within the last year.
+    pub recent: u32,
+    pub banned: u32,
+}
+
+impl Activity {
+    /// The share of sampled members with recent accounts, from 0 to 1.
+    pub fn estimate(&self) -> f64 {
+        if self.sampled == 0 {
+            return 0.;
+        }
+
+        self.recent as f64 / self.sampled as f64
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct MembersResponse {
+    data: Vec<Member>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Member {
+    user: MemberUser,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct MemberUser {
+    user_id: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    created: Option<String>,
+    #[serde(default)]
+    is_banned: bool,
+}
+
+/// Samples `size` members from the newest page of the group's members and
+/// looks up their profiles. Profiles that fail to load are left out.
+pub async fn sample(
+    group_id: u32,
+    size: usize,
+    args: &Args,
+    api: &Api,
+) -> Result<Activity, Box<dyn std::error::Error>> {
+    let members = api
+        .get(
+            &format!("/v1/groups/{}/users", group_id),
+            &[("limit", "100"), ("sortOrder", "Desc")],
+        )
+        .await?
+        .json::<MembersResponse>()
+        .await?
+        .data;
+
+    let user_ids: Vec<u64> = members
+        .choose_multiple(&mut rand::thread_rng(), size)
+        .map(|member| member.user.user_id)
+        .collect();
+
+    let now = SystemTime::now();
+    let mut activity = Activity::default();
+
+    for user_id in user_ids {
+        let Ok(profile) = fetch_profile(api, args, user_id).await else {
+            continue;
+        };
+
+        activity.sampled += 1;
+
+        if profile.is_banned {
+            activity.banned += 1;
+        }
+
+        if profile
+            .created
+            .as_deref()
+            .and_then(|created| humantime::parse_rfc3339_weak(created).ok())
+            .and_then(|created| now.duration_since(created).ok())
+            .is_some_and(|age| age <= RECENT)
+        {
+            activity.recent += 1;
+        }
+    }
+
+    Ok(activity)
+}
+
+async fn fetch_profile(
+    api: &Api,
+    args: &Args,
+    user_id: u64,
+) -> Result<Profile, Box<dyn std::error::Error>> {
+    api.get_url_json(
+        &format!("{}/v1/users/{}", args.users_api_domain, user_id),
+        &[],
+    )
+    .await
+}
@@ -0,0 +1,344 @@
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::ids::{self, IdRange};
+use crate::store::{self, Category};
+use crate::Args;
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct AnalyzeArgs {
+    /// How many group ids each bucket spans
+    #[arg(
+        long,
+        default_value_t = 1_000_000,
+        env = "RECLAIMER_ANALYZE_BUCKET_SIZE"
+    )]
+    bucket_size: u32,
+
+    /// Print the buckets as CSV instead of a histogram
+    #[arg(long, env = "RECLAIMER_ANALYZE_CSV")]
+    csv: bool,
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct StoreArgs {
+    /// The store to analyze, such as a groups.json copied from another machine
+    path: PathBuf,
+
+    /// How many group ids each bucket of the histogram spans
+    #[arg(
+        long,
+        default_value_t = 1_000_000,
+        env = "RECLAIMER_ANALYZE_STORE_BUCKET_SIZE"
+    )]
+    bucket_size: u32,
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    checked: u64,
+    found: u64,
+    nonexistent: u64,
+}
+
+impl Bucket {
+    fn rate(&self) -> f64 {
+        if self.checked == 0 {
+            return 0.;
+        }
+
+        self.found as f64 / self.checked as f64
+    }
+}
+
+/// Width of the longest histogram bar.
+const BAR_WIDTH: usize = 40;
+
+/// Ids of groups reported as found in the log file, taken from the group link
+/// at the end of each `FOUND` line.
+fn found_group_ids(log: &str) -> HashSet<u32> {
+    log.lines()
+        .filter(|line| line.split_whitespace().nth(1) == Some("FOUND"))
+        .filter_map(|line| {
+            line.split("https://www.roblox.com/groups/")
+                .nth(1)?
+                .split(|char: char| !char.is_ascii_digit())
+                .next()?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// Prints how many of the checked groups in the store were found available,
+/// per bucket of group ids. Findings from before they were recorded in the
+/// store are read from `--log-file`.
+pub fn analyze(args: &Args, analyze_args: &AnalyzeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = store::load(&args.store)?;
+
+    let found = match &args.log_file {
+        Some(log_file) => found_group_ids(&fs::read_to_string(log_file).unwrap_or_default()),
+        // Only stores written before findings were categorised need the log.
+        None if entries
+            .iter()
+            .any(|entry| entry.category == Category::FoundAvailable) =>
+        {
+            HashSet::new()
+        }
+        None => {
+            println!(
+                "{}",
+                "No --log-file given, availability rates need the findings it records".yellow()
+            );
+            HashSet::new()
+        }
+    };
+
+    let bucket_size = analyze_args.bucket_size.max(1);
+    let mut buckets: BTreeMap<u32, Bucket> = BTreeMap::new();
+
+    for entry in entries {
+        let bucket = buckets.entry(entry.id / bucket_size).or_default();
+
+        match entry.category {
+            Category::Nonexistent => bucket.nonexistent += 1,
+            category => {
+                bucket.checked += 1;
+
+                if category == Category::FoundAvailable || found.contains(&entry.id) {
+                    bucket.found += 1;
+                }
+            }
+        }
+    }
+
+    if analyze_args.csv {
+        println!("start,end,checked,found,rate,nonexistent");
+
+        for (index, bucket) in buckets.iter() {
+            let start = *index as u64 * bucket_size as u64;
+
+            println!(
+                "{},{},{},{},{:.6},{}",
+                start,
+                start + bucket_size as u64 - 1,
+                bucket.checked,
+                bucket.found,
+                bucket.rate(),
+                bucket.nonexistent
+            );
+        }
+
+        return Ok(());
+    }
+
+    let separator = "│".truecolor(140, 140, 140);
+    let max_rate = buckets
+        .values()
+        .map(|bucket| bucket.rate())
+        .fold(0., f64::max);
+
+    println!(
+        "{:<25} {separator} {:>11} {separator} {:>8} {separator} {:>6} {separator} {:>7} {separator}",
+        "Ids", "nonexistent", "checked", "found", "rate"
+    );
+
+    for (index, bucket) in buckets.iter() {
+        let start = *index as u64 * bucket_size as u64;
+        let bar = if max_rate > 0. {
+            (bucket.rate() / max_rate * BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+
+        println!(
+            "{:<25} {separator} {:>11} {separator} {:>8} {separator} {:>6} {separator} {:>7} {separator} {}",
+            format!("{}-{}", start, start + bucket_size as u64 - 1),
+            bucket.nonexistent,
+            bucket.checked,
+            bucket.found,
+            format!("{:.2}%", bucket.rate() * 100.),
+            "█".repeat(bar).green()
+        );
+    }
+
+    Ok(())
+}
+
+/// Prints what a store holds without any network access: how many groups are
+/// in each category, how they are spread across ids, findings per day and
+/// how much of the --min to --max range is left to scan.
+pub fn analyze_store(
+    args: &Args,
+    store_args: &StoreArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = store::load(&store_args.path)?;
+    let separator = "│".truecolor(140, 140, 140);
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            format!("{} is empty", store_args.path.display()).yellow()
+        );
+        return Ok(());
+    }
+
+    let mut categories: BTreeMap<Category, u64> = BTreeMap::new();
+    for entry in &entries {
+        *categories.entry(entry.category).or_default() += 1;
+    }
+
+    println!(
+        "{:<16} {separator} {:>9} {separator} {:>7}",
+        "Category", "groups", "share"
+    );
+
+    for (category, count) in categories.iter() {
+        println!(
+            "{:<16} {separator} {:>9} {separator} {:>7}",
+            category.to_string(),
+            count,
+            format!("{:.2}%", *count as f64 / entries.len() as f64 * 100.)
+        );
+    }
+
+    let bucket_size = store_args.bucket_size.max(1);
+    let mut buckets: BTreeMap<u32, u64> = BTreeMap::new();
+    for entry in &entries {
+        *buckets.entry(entry.id / bucket_size).or_default() += 1;
+    }
+
+    let most = buckets.values().copied().max().unwrap_or_default();
+
+    println!();
+    println!("{:<25} {separator} {:>9} {separator}", "Ids", "groups");
+
+    for (index, count) in buckets.iter() {
+        let start = *index as u64 * bucket_size as u64;
+
+        println!(
+            "{:<25} {separator} {:>9} {separator} {}",
+            format!("{}-{}", start, start + bucket_size as u64 - 1),
+            count,
+            "█"
+                .repeat((*count as f64 / most as f64 * BAR_WIDTH as f64).round() as usize)
+                .green()
+        );
+    }
+
+    // Days are the date part of the RFC 3339 timestamps, in UTC.
+    let mut days: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for entry in &entries {
+        if let Some(day) = entry.at.as_deref().and_then(|at| at.get(..10)) {
+            let (checked, found) = days.entry(day).or_default();
+            *checked += 1;
+
+            if entry.category == Category::FoundAvailable {
+                *found += 1;
+            }
+        }
+    }
+
+    if !days.is_empty() {
+        println!();
+        println!(
+            "{:<10} {separator} {:>9} {separator} {:>6} {separator}",
+            "Day", "checked", "found"
+        );
+
+        for (day, (checked, found)) in days.iter() {
+            println!(
+                "{:<10} {separator} {:>9} {separator} {:>6} {separator}",
+                day, checked, found
+            );
+        }
+    }
+
+    let ranges = ids::ranges(args);
+    let in_ranges = |id: &u32| {
+        ranges
+            .iter()
+            .any(|range| (range.start..=range.end).contains(id))
+    };
+
+    let range = union_len(&ranges);
+    let scanned = entries
+        .iter()
+        .map(|entry| entry.id)
+        .filter(in_ranges)
+        .collect::<HashSet<u32>>()
+        .len() as u64;
+    let remaining = range.saturating_sub(scanned);
+
+    let spans = ranges
+        .iter()
+        .map(|range| format!("{}-{}", range.start, range.end))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    println!();
+    println!(
+        "{} of the {} ids in {} scanned ({:.4}%), {} left",
+        scanned,
+        range,
+        spans,
+        scanned as f64 / range as f64 * 100.,
+        remaining
+    );
+
+    if let Some(pace) = recent_pace(&entries) {
+        println!(
+            "At the last day's pace of {:.0} ids/hour that's about {} to go",
+            pace,
+            humantime::format_duration(Duration::from_secs(
+                (remaining as f64 / pace * 3600.) as u64
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+/// How many ids the ranges span, counting ids in overlapping ranges once.
+fn union_len(ranges: &[IdRange]) -> u64 {
+    let mut spans: Vec<(u64, u64)> = ranges
+        .iter()
+        .map(|range| (range.start as u64, range.end as u64))
+        .collect();
+    spans.sort_unstable();
+
+    let mut len = 0;
+    let mut covered_to: Option<u64> = None;
+
+    for (start, end) in spans {
+        let start = covered_to.map_or(start, |covered_to| start.max(covered_to + 1));
+
+        if start <= end {
+            len += end - start + 1;
+        }
+
+        covered_to = Some(covered_to.map_or(end, |covered_to| covered_to.max(end)));
+    }
+
+    len
+}
+
+/// Ids added to the store per hour over the day before its latest entry.
+fn recent_pace(entries: &[store::Entry]) -> Option<f64> {
+    let times: Vec<SystemTime> = entries
+        .iter()
+        .filter_map(|entry| humantime::parse_rfc3339(entry.at.as_deref()?).ok())
+        .collect();
+
+    let latest = times.iter().max()?;
+    let since = *latest - Duration::from_secs(24 * 3600);
+    let recent: Vec<&SystemTime> = times.iter().filter(|time| **time > since).collect();
+    let earliest = recent.iter().min()?;
+    let hours = latest.duration_since(**earliest).ok()?.as_secs_f64() / 3600.;
+
+    (hours > 0.).then(|| recent.len() as f64 / hours)
+}
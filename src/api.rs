@@ -0,0 +1,394 @@
+use reqwest::{Client, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::breaker::CircuitBreaker;
+use crate::budget::SharedBudget;
+use crate::cache::Cache;
+use crate::errors::{ApiError, ErrorKind, ErrorResponse};
+use crate::proxy::{Outcome, ProxyPool};
+use crate::statsd::Statsd;
+use crate::Args;
+use crate::{httplog, output, systemd};
+
+/// How long a failing domain is skipped before it is tried again.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Request outcome counters for the lifetime of the [`Api`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ApiStats {
+    pub requests: u64,
+    pub errors: u64,
+    pub consecutive_errors: u64,
+}
+
+impl ApiStats {
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.;
+        }
+
+        self.errors as f64 / self.requests as f64
+    }
+}
+
+/// Sends requests to the group api, failing over between the configured
+/// domains in order whenever one of them is unreachable or erroring.
+pub struct Api {
+    /// Client for requests that shouldn't go through the proxies.
+    pub client: Client,
+    /// Clients group api requests are rotated across, one per proxy.
+    pub proxies: ProxyPool,
+    pub statsd: Statsd,
+    domains: Vec<String>,
+    unhealthy_until: RefCell<Vec<Option<Instant>>>,
+    breaker: RefCell<CircuitBreaker>,
+    stats: RefCell<ApiStats>,
+    cache: Option<Cache>,
+    min_interval: Option<Duration>,
+    last_request: Cell<Option<Instant>>,
+    /// Takes the place of `min_interval` with --shared-budget.
+    shared_budget: Option<SharedBudget>,
+}
+
+impl Api {
+    pub fn new(
+        client: Client,
+        proxied_clients: Vec<(String, Client)>,
+        args: &Args,
+    ) -> std::io::Result<Self> {
+        let shared_budget = match (&args.shared_budget, args.rps) {
+            (Some(path), Some(rps)) if rps > 0. => Some(SharedBudget::new(path, rps)?),
+            _ => None,
+        };
+
+        let cache = match &args.cache_dir {
+            Some(cache_dir) if !args.no_cache => {
+                Some(Cache::new(cache_dir.clone(), args.cache_ttl)?)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            proxies: ProxyPool::new(
+                client.clone(),
+                proxied_clients,
+                args.proxy_rps,
+                args.proxy_max_failures,
+                args.proxy_quarantine,
+            ),
+            client,
+            statsd: Statsd::new(
+                args.statsd_addr.as_deref(),
+                &args.statsd_prefix,
+                &args.statsd_tags,
+            )?,
+            domains: args
+                .group_api_domain
+                .iter()
+                .map(|domain| domain.trim_end_matches('/').to_string())
+                .collect(),
+            unhealthy_until: RefCell::new(vec![None; args.group_api_domain.len()]),
+            breaker: RefCell::new(CircuitBreaker::new(
+                args.breaker_threshold,
+                args.breaker_cooldown,
+            )),
+            stats: RefCell::new(ApiStats::default()),
+            cache,
+            min_interval: args
+                .rps
+                .filter(|rps| *rps > 0.)
+                .map(|rps| Duration::from_secs_f64(1. / rps)),
+            last_request: Cell::new(None),
+            shared_budget,
+        })
+    }
+
+    /// The next client in the proxy rotation.
+    pub fn next_client(&self) -> Client {
+        self.proxies.client(self.proxies.pick())
+    }
+
+    pub fn stats(&self) -> ApiStats {
+        *self.stats.borrow()
+    }
+
+    pub async fn get(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        self.paced(self.send(path, query)).await
+    }
+
+    /// Sends a GET request to an absolute url outside the group api, such as
+    /// Open Cloud or the users api, paced and counted like any other request.
+    pub async fn get_url(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        self.paced(self.send_url(url, headers)).await
+    }
+
+    /// Waits out the circuit breaker and --rps before sending `request`, then
+    /// records how it went.
+    async fn paced(
+        &self,
+        request: impl Future<Output = Result<Response, Box<dyn std::error::Error>>>,
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        let delay = self.breaker.borrow_mut().delay();
+
+        if let Some(delay) = delay {
+            output::verbose(
+                output::RETRIES,
+                &format!(
+                    "Waiting {} for the circuit breaker",
+                    humantime::format_duration(delay)
+                ),
+            );
+            systemd::sleep(delay).await;
+        }
+
+        // Paces this process alone if the budget file can't be used.
+        let shared_wait = self
+            .shared_budget
+            .as_ref()
+            .and_then(|shared_budget| shared_budget.take().ok());
+
+        if let Some(wait) = shared_wait {
+            systemd::sleep(wait).await;
+        } else if let (Some(min_interval), Some(last_request)) =
+            (self.min_interval, self.last_request.get())
+        {
+            systemd::sleep(min_interval.saturating_sub(last_request.elapsed())).await;
+        }
+
+        self.last_request.set(Some(Instant::now()));
+
+        let started = Instant::now();
+        let result = request.await;
+
+        self.statsd.increment("requests");
+        self.statsd.timing("request.latency", started.elapsed());
+
+        let success = match &result {
+            Ok(response) => {
+                !response.status().is_server_error()
+                    && response.status() != StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(_) => false,
+        };
+
+        self.breaker.borrow_mut().record(success);
+
+        let mut stats = self.stats.borrow_mut();
+        stats.requests += 1;
+
+        if success {
+            stats.consecutive_errors = 0;
+        } else {
+            stats.errors += 1;
+            stats.consecutive_errors += 1;
+            self.statsd.increment("errors");
+        }
+
+        result
+    }
+
+    /// Sends a GET request and deserializes its body, serving it from the
+    /// response cache when one is configured.
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let key = format!(
+            "{}?{}",
+            path,
+            query
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<String>>()
+                .join("&")
+        );
+
+        if let Some(value) = self.cached(&key) {
+            return Ok(value);
+        }
+
+        let response = self.get(path, query).await?;
+        let success = response.status().is_success();
+        let body = response.text().await?;
+
+        if !success {
+            if let Ok(response) = serde_json::from_str::<ErrorResponse>(&body) {
+                let err = ApiError::from(response.errors);
+                self.handle_error(&err);
+                return Err(err.into());
+            }
+        }
+
+        let value = serde_json::from_str(&body)?;
+
+        if let Some(cache) = self.cache.as_ref().filter(|_| success) {
+            cache.put(&key, &body);
+        }
+
+        Ok(value)
+    }
+
+    /// Like [`Api::get_json`] for [`Api::get_url`], except any unsuccessful
+    /// status is an error, so a missing resource can be told apart from one
+    /// that failed to load.
+    pub async fn get_url_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        if let Some(value) = self.cached(url) {
+            return Ok(value);
+        }
+
+        let response = self.get_url(url, headers).await?;
+        let status_error = response.error_for_status_ref().err();
+        let body = response.text().await?;
+
+        if let Some(status_error) = status_error {
+            if let Ok(response) = serde_json::from_str::<ErrorResponse>(&body) {
+                let err = ApiError::from(response.errors);
+                self.handle_error(&err);
+                return Err(err.into());
+            }
+
+            return Err(status_error.into());
+        }
+
+        let value = serde_json::from_str(&body)?;
+
+        if let Some(cache) = self.cache.as_ref() {
+            cache.put(url, &body);
+        }
+
+        Ok(value)
+    }
+
+    fn cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let body = self.cache.as_ref()?.get(key)?;
+        serde_json::from_str(&body).ok()
+    }
+
+    /// Backs off when Roblox says requests are coming in too fast.
+    pub fn handle_error(&self, err: &ApiError) {
+        if err.kind == ErrorKind::TooManyRequests {
+            self.breaker.borrow_mut().trip("after being rate limited");
+        }
+    }
+
+    async fn send(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        let order = self.domain_order();
+        let mut last_error: Option<Box<dyn std::error::Error>> = None;
+
+        for (position, index) in order.iter().enumerate() {
+            let mut url = Url::parse(format!("{}{}", self.domains[*index], path).as_str())?;
+
+            if !query.is_empty() {
+                url.query_pairs_mut().extend_pairs(query);
+            }
+
+            let proxy = self.proxies.pick();
+            self.proxies.wait(proxy).await;
+
+            let result = httplog::send(self.proxies.client(proxy).get(url)).await;
+
+            self.proxies.record(proxy, outcome(&result));
+
+            match result {
+                Ok(response)
+                    if response.status().is_server_error() && position + 1 < order.len() =>
+                {
+                    output::verbose(
+                        output::RETRIES,
+                        &format!(
+                            "{} responded with {}, failing over to {}",
+                            self.domains[*index],
+                            response.status(),
+                            self.domains[order[position + 1]]
+                        ),
+                    );
+                    self.mark_unhealthy(*index);
+                }
+                Ok(response) => {
+                    if !response.status().is_server_error() {
+                        self.unhealthy_until.borrow_mut()[*index] = None;
+                    }
+
+                    return Ok(response);
+                }
+                Err(err) => {
+                    output::verbose(
+                        output::RETRIES,
+                        &format!("{} failed: {}", self.domains[*index], err),
+                    );
+                    self.mark_unhealthy(*index);
+                    last_error = Some(err.into());
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| "No group api domains configured".into()))
+    }
+
+    async fn send_url(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn std::error::Error>> {
+        let url = Url::parse(url)?;
+
+        let proxy = self.proxies.pick();
+        self.proxies.wait(proxy).await;
+
+        let mut request = self.proxies.client(proxy).get(url);
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        let result = httplog::send(request).await;
+        self.proxies.record(proxy, outcome(&result));
+
+        Ok(result?)
+    }
+
+    /// Healthy domains in their configured order, followed by the ones still
+    /// cooling down so a request is attempted even if every domain failed.
+    fn domain_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let unhealthy_until = self.unhealthy_until.borrow();
+
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..self.domains.len())
+            .partition(|index| unhealthy_until[*index].is_none_or(|until| until <= now));
+
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    fn mark_unhealthy(&self, index: usize) {
+        self.unhealthy_until.borrow_mut()[index] = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+    }
+}
+
+fn outcome(result: &reqwest::Result<Response>) -> Outcome {
+    match result {
+        Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => Outcome::RateLimited,
+        Ok(response) if response.status().is_server_error() => Outcome::Error,
+        Ok(_) => Outcome::Success,
+        Err(_) => Outcome::Error,
+    }
+}
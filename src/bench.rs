@@ -0,0 +1,179 @@
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use reqwest::{StatusCode, Url};
+use std::time::{Duration, Instant};
+
+use crate::api::Api;
+use crate::Args;
+use crate::{httplog, ids};
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct BenchArgs {
+    /// How many requests to time per endpoint
+    #[arg(long, default_value_t = 20, env = "RECLAIMER_BENCH_SAMPLES")]
+    samples: usize,
+
+    /// How long to hold each request rate for while probing
+    #[arg(
+        long,
+        default_value = "5s",
+        value_parser = humantime::parse_duration,
+        env = "RECLAIMER_BENCH_PROBE_DURATION"
+    )]
+    probe_duration: Duration,
+
+    /// The highest request rate to probe
+    #[arg(long, default_value_t = 64., env = "RECLAIMER_BENCH_MAX_RPS")]
+    max_rps: f64,
+
+    /// Keyword to time the search endpoint with
+    #[arg(long, default_value_t = String::from("roblox"), env = "RECLAIMER_BENCH_KEYWORD")]
+    keyword: String,
+}
+
+struct Sample {
+    latency: Duration,
+    status: Option<StatusCode>,
+}
+
+fn percentile(latencies: &[Duration], percentile: f64) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let index = ((percentile * latencies.len() as f64).ceil() as usize).clamp(1, latencies.len());
+    latencies[index - 1]
+}
+
+async fn sample(api: &Api, url: &str) -> Sample {
+    let started = Instant::now();
+    let status = httplog::send(api.next_client().get(url))
+        .await
+        .ok()
+        .map(|response| response.status());
+
+    Sample {
+        latency: started.elapsed(),
+        status,
+    }
+}
+
+fn random_group_url(args: &Args) -> String {
+    format!(
+        "{}/v1/groups/{}",
+        args.group_api_domain[0].trim_end_matches('/'),
+        ids::random_id(&ids::ranges(args))
+    )
+}
+
+/// Measures latency percentiles per endpoint, then raises the request rate
+/// until the api starts rate limiting to suggest a safe `--rps`.
+pub async fn bench(args: &Args, bench_args: &BenchArgs, api: &Api) {
+    let domain = args.group_api_domain[0].trim_end_matches('/');
+    let separator = "│".truecolor(140, 140, 140);
+
+    println!(
+        "{:<16} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>6}",
+        "Endpoint", "p50", "p90", "p99", "max", "errors"
+    );
+
+    for endpoint in ["details", "relationships", "roles", "search"] {
+        let mut latencies: Vec<Duration> = vec![];
+        let mut errors = 0;
+
+        for _ in 0..bench_args.samples {
+            let url = match endpoint {
+                "details" => random_group_url(args),
+                "relationships" => {
+                    format!(
+                        "{}/relationships/allies?StartRowIndex=1&MaxRows=100",
+                        random_group_url(args)
+                    )
+                }
+                "roles" => format!("{}/roles", random_group_url(args)),
+                _ => Url::parse_with_params(
+                    format!("{}/v1/groups/search", domain).as_str(),
+                    &[("keyword", bench_args.keyword.as_str()), ("limit", "10")],
+                )
+                .map(String::from)
+                .unwrap_or_default(),
+            };
+
+            let sample = sample(api, &url).await;
+
+            if !sample.status.is_some_and(|status| status.is_success()) {
+                errors += 1;
+            }
+
+            latencies.push(sample.latency);
+        }
+
+        latencies.sort();
+
+        println!(
+            "{:<16} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>6}",
+            endpoint,
+            format!("{}ms", percentile(&latencies, 0.5).as_millis()),
+            format!("{}ms", percentile(&latencies, 0.9).as_millis()),
+            format!("{}ms", percentile(&latencies, 0.99).as_millis()),
+            format!("{}ms", latencies.last().copied().unwrap_or_default().as_millis()),
+            errors
+        );
+    }
+
+    let mut rps = 1.;
+    let mut safe_rps: Option<f64> = None;
+
+    while rps <= bench_args.max_rps {
+        let started = Instant::now();
+        let mut sent = 0;
+        let mut rate_limited = 0;
+
+        while started.elapsed() < bench_args.probe_duration {
+            let sample = sample(api, &random_group_url(args)).await;
+            sent += 1;
+
+            if sample.status == Some(StatusCode::TOO_MANY_REQUESTS) {
+                rate_limited += 1;
+            }
+
+            let next = Duration::from_secs_f64(sent as f64 / rps);
+            tokio::time::sleep(next.saturating_sub(started.elapsed())).await;
+        }
+
+        let achieved = sent as f64 / started.elapsed().as_secs_f64();
+
+        println!(
+            "Probed {:.1} req/s (achieved {:.1}): {}",
+            rps,
+            achieved,
+            if rate_limited > 0 {
+                format!("{} of {} requests rate limited", rate_limited, sent).red()
+            } else {
+                "no rate limiting".green()
+            }
+        );
+
+        if rate_limited > 0 {
+            break;
+        }
+
+        safe_rps = Some(achieved);
+        rps *= 2.;
+    }
+
+    match safe_rps {
+        Some(safe_rps) => println!(
+            "{}",
+            format!(
+                "Suggested: --rps {:.1} (80% of the highest rate without rate limiting)",
+                safe_rps * 0.8
+            )
+            .yellow()
+        ),
+        None => println!(
+            "{}",
+            "Rate limited even at 1 req/s, try again later or use another domain".red()
+        ),
+    }
+}
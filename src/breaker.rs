@@ -0,0 +1,117 @@
+use colored::Colorize;
+use std::time::{Duration, Instant};
+
+use crate::{logfile, output};
+
+/// The longest the breaker will back off for between probes.
+const MAX_COOLDOWN: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are paused until the cooldown elapses.
+    Open { until: Instant },
+    /// A single probe request is allowed through to test the api.
+    HalfOpen,
+}
+
+/// Pauses scanning after too many consecutive failed requests, backing off
+/// exponentially between probes until the api recovers.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    base_cooldown: Duration,
+    cooldown: Duration,
+    failures: u32,
+    state: BreakerState,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            base_cooldown: cooldown,
+            cooldown,
+            failures: 0,
+            state: BreakerState::Closed,
+        }
+    }
+
+    /// How long to wait before the next request may be sent.
+    pub fn delay(&mut self) -> Option<Duration> {
+        let BreakerState::Open { until } = self.state else {
+            return None;
+        };
+
+        let message = "Circuit breaker half-open, probing the group api";
+        output::info(message.yellow());
+        logfile::log("WARN", message);
+
+        self.state = BreakerState::HalfOpen;
+
+        Some(until.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn record(&mut self, success: bool) {
+        if self.threshold == 0 {
+            return;
+        }
+
+        if success {
+            if self.state == BreakerState::HalfOpen {
+                let message = "Circuit breaker closed, resuming scan";
+                output::info(message.green());
+                logfile::log("INFO", message);
+            }
+
+            self.failures = 0;
+            self.cooldown = self.base_cooldown;
+            self.state = BreakerState::Closed;
+
+            return;
+        }
+
+        self.failures += 1;
+
+        match self.state {
+            BreakerState::HalfOpen => {
+                self.cooldown = (self.cooldown * 2).min(MAX_COOLDOWN);
+                self.open(&format!("after {} consecutive failures", self.failures));
+            }
+            BreakerState::Closed if self.failures >= self.threshold => {
+                self.open(&format!("after {} consecutive failures", self.failures))
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the breaker right away, for responses that say outright to back
+    /// off rather than failing.
+    pub fn trip(&mut self, reason: &str) {
+        if self.threshold == 0 || matches!(self.state, BreakerState::Open { .. }) {
+            return;
+        }
+
+        if self.state == BreakerState::HalfOpen {
+            self.cooldown = (self.cooldown * 2).min(MAX_COOLDOWN);
+        }
+
+        self.open(reason);
+    }
+
+    fn open(&mut self, reason: &str) {
+        let message = format!(
+            "Circuit breaker open {}, pausing for {}",
+            reason,
+            humantime::format_duration(self.cooldown)
+        );
+
+        output::info(message.red());
+        logfile::log("ERROR", &message);
+
+        self.state = BreakerState::Open {
+            until: Instant::now() + self.cooldown,
+        };
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A token bucket kept in a file, so every process pointed at the same file
+/// shares one request rate. Each request reserves a token under a file lock,
+/// waiting out its turn if the bucket is overdrawn.
+#[derive(Debug)]
+pub struct SharedBudget {
+    path: PathBuf,
+    rate: f64,
+    /// How many requests can go out back to back after the bucket refilled.
+    burst: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Bucket {
+    tokens: f64,
+    /// Unix time in seconds the tokens were counted at.
+    updated: f64,
+}
+
+impl SharedBudget {
+    pub fn new(path: &Path, rate: f64) -> std::io::Result<Self> {
+        // Fails early on a path that can't be written to.
+        open(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            rate,
+            burst: rate.max(1.),
+        })
+    }
+
+    /// Reserves a token, returning how long to wait before sending the
+    /// request it's for.
+    pub fn take(&self) -> std::io::Result<Duration> {
+        let mut file = open(&self.path)?;
+        file.lock()?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // An empty or unreadable file starts out full.
+        let mut bucket = serde_json::from_str::<Bucket>(&contents).unwrap_or(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+
+        bucket.tokens =
+            (bucket.tokens + (now - bucket.updated).max(0.) * self.rate).min(self.burst) - 1.;
+        bucket.updated = now;
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&bucket)?.as_bytes())?;
+
+        // The lock is released when the file is closed.
+        Ok(Duration::from_secs_f64(
+            (-bucket.tokens).max(0.) / self.rate,
+        ))
+    }
+}
+
+fn open(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
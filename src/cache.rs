@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Stores response bodies on disk keyed by request, serving them back until
+/// they are older than the ttl.
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> std::io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self { dir, ttl })
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.path(key);
+        let modified = fs::metadata(&path).ok()?.modified().ok()?;
+
+        if SystemTime::now().duration_since(modified).ok()? > self.ttl {
+            return None;
+        }
+
+        fs::read_to_string(path).ok()
+    }
+
+    pub fn put(&self, key: &str, body: &str) {
+        fs::write(self.path(key), body).ok();
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+}
+
+/// A stable hash, unlike `DefaultHasher` which may change between releases.
+fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Group, User};
+
+/// A group as returned by the Open Cloud v2 communities API.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudGroup {
+    path: String,
+    create_time: Option<String>,
+    update_time: Option<String>,
+    id: String,
+    display_name: String,
+    #[serde(default)]
+    description: String,
+    owner: Option<String>,
+    #[serde(default)]
+    member_count: u32,
+    #[serde(default)]
+    public_entry_allowed: bool,
+    #[serde(default)]
+    locked: bool,
+    #[serde(default)]
+    verified: bool,
+}
+
+impl TryFrom<CloudGroup> for Group {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(group: CloudGroup) -> Result<Self, Self::Error> {
+        let owner = group.owner.map(|owner| User {
+            has_verified_badge: false,
+            user_id: owner
+                .trim_start_matches("users/")
+                .parse()
+                .unwrap_or_default(),
+            username: String::new(),
+            display_name: String::new(),
+        });
+
+        Ok(Group {
+            id: group.id.parse()?,
+            name: group.display_name,
+            description: group.description,
+            owner,
+            shout: None,
+            member_count: group.member_count,
+            is_builders_club_only: false,
+            public_entry_allowed: group.public_entry_allowed,
+            is_locked: group.locked.then_some(true),
+            has_verified_badge: group.verified,
+            enrichment: Default::default(),
+            tags: vec![],
+            language: None,
+            activity: None,
+            previous_name: None,
+            created: group.create_time,
+            updated: group.update_time,
+        })
+    }
+}
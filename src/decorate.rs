@@ -0,0 +1,103 @@
+use clap::ValueEnum;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Request;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::sha256;
+
+/// Adjusts every outgoing request before it is sent, for gateways that
+/// need requests identified or signed.
+pub trait RequestDecorator: Send + Sync {
+    fn decorate(&self, request: &mut Request);
+}
+
+/// Set once at startup from the arguments.
+static DECORATORS: OnceLock<Vec<Box<dyn RequestDecorator>>> = OnceLock::new();
+
+pub fn init(decorators: Vec<Box<dyn RequestDecorator>>) {
+    DECORATORS.set(decorators).ok();
+}
+
+/// Runs a request through every configured decorator.
+pub fn apply(request: &mut Request) {
+    for decorator in DECORATORS.get().into_iter().flatten() {
+        decorator.decorate(request);
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    HmacSha256,
+}
+
+/// Signs requests with an HMAC of
+/// `METHOD\nPATH?QUERY\nTIMESTAMP\nSHA256(BODY)`, sending the hex signature
+/// in `header` and the unix timestamp in `<header>-Timestamp`.
+pub struct Signer {
+    secret: String,
+    header: HeaderName,
+    timestamp_header: HeaderName,
+    algorithm: SignatureAlgorithm,
+}
+
+impl Signer {
+    pub fn new(
+        secret: String,
+        header: &str,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            secret,
+            header: HeaderName::from_bytes(header.as_bytes())?,
+            timestamp_header: HeaderName::from_bytes(format!("{}-Timestamp", header).as_bytes())?,
+            algorithm,
+        })
+    }
+}
+
+impl RequestDecorator for Signer {
+    fn decorate(&self, request: &mut Request) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let url = request.url();
+        let target = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .unwrap_or_default();
+
+        let canonical = format!(
+            "{}\n{}\n{}\n{}",
+            request.method(),
+            target,
+            timestamp,
+            sha256::hex_digest(body)
+        );
+
+        let signature = match self.algorithm {
+            SignatureAlgorithm::HmacSha256 => {
+                sha256::hmac(self.secret.as_bytes(), canonical.as_bytes())
+            }
+        };
+
+        let signature: String = signature
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        let headers = request.headers_mut();
+        headers.insert(
+            self.header.clone(),
+            HeaderValue::from_str(&signature).expect("hex is a valid header value"),
+        );
+        headers.insert(self.timestamp_header.clone(), HeaderValue::from(timestamp));
+    }
+}
@@ -0,0 +1,131 @@
+use clap::ValueEnum;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::Api;
+use crate::{Args, Group, Role, SocialLink};
+
+/// How much is gathered about each available group.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// Details, social links and, with --roles, roles
+    Basic,
+    /// Everything in basic plus roles, games, store items and the emblem,
+    /// saved as one JSON dossier per group
+    Full,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Game {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub place_visits: u64,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StoreItem {
+    pub id: u64,
+    pub item_type: String,
+    pub name: String,
+    pub price: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DataResponse<T> {
+    data: Vec<T>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Thumbnail {
+    image_url: Option<String>,
+}
+
+/// Everything known about a group, to judge a claim from one file.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Dossier<'a> {
+    pub group: &'a Group,
+    pub score: u32,
+    pub social_links: &'a [SocialLink],
+    pub roles: &'a [Role],
+    pub games: Vec<Game>,
+    pub store_items: Vec<StoreItem>,
+    pub emblem: Option<String>,
+}
+
+async fn fetch_data<T: DeserializeOwned>(
+    api: &Api,
+    url: String,
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    Ok(api.get_url_json::<DataResponse<T>>(&url, &[]).await?.data)
+}
+
+/// Fetches the games, store items and emblem the group api doesn't return.
+/// Parts that fail to load are left empty rather than failing the dossier.
+pub async fn gather<'a>(
+    group: &'a Group,
+    score: u32,
+    social_links: &'a [SocialLink],
+    roles: &'a [Role],
+    args: &Args,
+    api: &Api,
+) -> Dossier<'a> {
+    let games = fetch_data::<Game>(
+        api,
+        format!(
+            "{}/v2/groups/{}/games?accessFilter=Public&limit=50",
+            args.games_api_domain, group.id
+        ),
+    )
+    .await
+    .unwrap_or_default();
+
+    let store_items = fetch_data::<StoreItem>(
+        api,
+        format!(
+            "{}/v1/search/items/details?Category=All&CreatorType=Group&CreatorTargetId={}&Limit=30",
+            args.catalog_api_domain, group.id
+        ),
+    )
+    .await
+    .unwrap_or_default();
+
+    let emblem = fetch_data::<Thumbnail>(
+        api,
+        format!(
+            "{}/v1/groups/icons?groupIds={}&size=420x420&format=Png",
+            args.thumbnails_api_domain, group.id
+        ),
+    )
+    .await
+    .ok()
+    .and_then(|thumbnails| thumbnails.into_iter().next()?.image_url);
+
+    Dossier {
+        group,
+        score,
+        social_links,
+        roles,
+        games,
+        store_items,
+        emblem,
+    }
+}
+
+/// Writes the dossier to `<dir>/<group id>.json`, returning its path.
+pub fn save(dossier: &Dossier, dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+
+    let path = dir.join(format!("{}.json", dossier.group.id));
+    fs::write(&path, serde_json::to_string_pretty(dossier)?)?;
+
+    Ok(path)
+}
@@ -0,0 +1,61 @@
+use reqwest::Client;
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+use crate::httplog;
+use crate::record::FindingRecord;
+
+/// Sends a finding to a user-configured endpoint and returns the JSON object
+/// it responds with, whose fields get merged into the finding.
+pub async fn enrich(
+    url: &str,
+    timeout: Duration,
+    finding: &FindingRecord,
+    client: &Client,
+) -> Result<Map<String, Value>, Box<dyn std::error::Error>> {
+    let response = httplog::send(client.post(url).timeout(timeout).json(finding))
+        .await?
+        .error_for_status()?;
+
+    match response.json::<Value>().await? {
+        Value::Object(fields) => Ok(fields),
+        _ => Err("enrichment response isn't a JSON object".into()),
+    }
+}
+
+/// Parses `FIELD` or `FIELD=VALUE` enrichment filters.
+pub fn parse_filter(value: &str) -> Result<(String, Option<String>), String> {
+    match value.split_once('=') {
+        Some((field, _)) if field.trim().is_empty() => {
+            Err(String::from("enrichment filter needs a field name"))
+        }
+        Some((field, value)) => Ok((field.trim().to_string(), Some(value.trim().to_string()))),
+        None if value.trim().is_empty() => {
+            Err(String::from("enrichment filter needs a field name"))
+        }
+        None => Ok((value.trim().to_string(), None)),
+    }
+}
+
+/// Whether the enriched fields pass every filter: a bare field has to be
+/// present and truthy, `FIELD=VALUE` has to equal the value.
+pub fn matches(fields: &Map<String, Value>, filters: &[(String, Option<String>)]) -> bool {
+    filters.iter().all(|(field, expected)| {
+        let Some(value) = fields.get(field) else {
+            return false;
+        };
+
+        match expected {
+            Some(expected) => format_value(value) == *expected,
+            None => !matches!(value, Value::Null | Value::Bool(false)),
+        }
+    })
+}
+
+/// Renders an enriched field's value for the terminal and log file.
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An entry of the `errors` array Roblox APIs respond with.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RobloxError {
+    pub code: u32,
+    pub message: String,
+    pub user_facing_message: Option<String>,
+}
+
+/// What a Roblox error means for the scan, most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ErrorKind {
+    /// A captcha or 2-step challenge, which can't be solved unattended.
+    ChallengeRequired,
+    /// The credentials were missing or rejected, which retrying won't fix.
+    Unauthorized,
+    /// Back off before sending more requests.
+    TooManyRequests,
+    /// The group was deleted or never existed, it never has to be checked again.
+    GroupNotFound,
+    /// The group was locked by moderation and can't be claimed.
+    GroupLocked,
+    Other,
+}
+
+impl RobloxError {
+    /// Codes are reused across endpoints with different meanings, so the
+    /// message is matched along with them.
+    pub fn kind(&self) -> ErrorKind {
+        let message = self.message.to_lowercase();
+
+        if message.contains("challenge") {
+            ErrorKind::ChallengeRequired
+        } else if message.contains("authorization has been denied") {
+            ErrorKind::Unauthorized
+        } else if message.replace(' ', "").contains("toomanyrequests") {
+            ErrorKind::TooManyRequests
+        } else if self.code == 1 && message.contains("does not exist") {
+            ErrorKind::GroupNotFound
+        } else if message.contains("locked") {
+            ErrorKind::GroupLocked
+        } else {
+            ErrorKind::Other
+        }
+    }
+}
+
+/// A response carrying Roblox errors instead of the expected body.
+#[derive(Debug)]
+pub struct ApiError {
+    pub kind: ErrorKind,
+    pub errors: Vec<RobloxError>,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages: Vec<String> = self
+            .errors
+            .iter()
+            .map(|error| format!("{} (code {})", error.message, error.code))
+            .collect();
+
+        f.write_str(&messages.join(", "))
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[derive(Deserialize, Debug)]
+pub struct ErrorResponse {
+    pub errors: Vec<RobloxError>,
+}
+
+impl From<Vec<RobloxError>> for ApiError {
+    fn from(errors: Vec<RobloxError>) -> Self {
+        Self {
+            kind: errors
+                .iter()
+                .map(RobloxError::kind)
+                .min()
+                .unwrap_or(ErrorKind::Other),
+            errors,
+        }
+    }
+}
+
+/// Turns the `errors` field of a response into an error if it has any.
+pub fn check(errors: Option<Vec<RobloxError>>) -> Result<(), ApiError> {
+    match errors {
+        Some(errors) if !errors.is_empty() => Err(errors.into()),
+        _ => Ok(()),
+    }
+}
+
+/// The kind of Roblox error behind `err`, if it is one.
+pub fn kind_of(err: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    err.downcast_ref::<ApiError>().map(|err| err.kind)
+}
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Related groups the crawler didn't get to because of `--max-traversal`,
+/// persisted so they are crawled later in this run or the next one.
+#[derive(Debug)]
+pub struct Frontier {
+    path: PathBuf,
+    group_ids: VecDeque<u32>,
+}
+
+impl Frontier {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let group_ids = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        } else {
+            VecDeque::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            group_ids,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.group_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.group_ids.is_empty()
+    }
+
+    /// Queues groups that aren't queued yet.
+    pub fn push(&mut self, group_ids: &[u32]) -> Result<(), Box<dyn std::error::Error>> {
+        for &group_id in group_ids {
+            if !self.group_ids.contains(&group_id) {
+                self.group_ids.push_back(group_id);
+            }
+        }
+
+        self.save()
+    }
+
+    pub fn pop(&mut self) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+        let group_id = self.group_ids.pop_front();
+
+        if group_id.is_some() {
+            self.save()?;
+        }
+
+        Ok(group_id)
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.group_ids.is_empty() {
+            if self.path.exists() {
+                fs::remove_file(&self.path)?;
+            }
+
+            return Ok(());
+        }
+
+        fs::write(&self.path, serde_json::to_string(&self.group_ids)?)?;
+
+        Ok(())
+    }
+}
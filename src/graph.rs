@@ -0,0 +1,223 @@
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::{Args, Group};
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct GraphArgs {
+    /// How many unclaimed groups to list
+    #[arg(long, default_value_t = 25, env = "RECLAIMER_ANALYZE_GRAPH_TOP")]
+    top: usize,
+
+    /// Only list unclaimed groups with at least this many relationships
+    #[arg(long, default_value_t = 1, env = "RECLAIMER_ANALYZE_GRAPH_MIN_DEGREE")]
+    min_degree: usize,
+
+    /// Print the ranking as CSV instead of a table
+    #[arg(long, env = "RECLAIMER_ANALYZE_GRAPH_CSV")]
+    csv: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Node {
+    id: u32,
+    name: String,
+    owned: bool,
+    member_count: u32,
+}
+
+impl From<&Group> for Node {
+    fn from(group: &Group) -> Self {
+        Self {
+            id: group.id,
+            name: group.name.clone(),
+            owned: group.owner.is_some(),
+            member_count: group.member_count,
+        }
+    }
+}
+
+/// A relationship seen while crawling, one JSON line in `--graph`.
+#[derive(Serialize, Deserialize, Debug)]
+struct Edge {
+    group: Node,
+    related: Node,
+    relationship: String,
+}
+
+/// Appends every ally and enemy relationship the crawler sees to `--graph`.
+#[derive(Debug)]
+pub struct GraphWriter {
+    file: Option<File>,
+}
+
+impl GraphWriter {
+    pub fn new(path: Option<&Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            file: path
+                .map(|path| OpenOptions::new().create(true).append(true).open(path))
+                .transpose()?,
+        })
+    }
+
+    pub fn record(&mut self, group: &Group, related: &Group, relationship: &str) {
+        let Some(file) = &mut self.file else {
+            return;
+        };
+
+        let edge = Edge {
+            group: group.into(),
+            related: related.into(),
+            relationship: relationship.to_lowercase(),
+        };
+
+        if let Ok(line) = serde_json::to_string(&edge) {
+            writeln!(file, "{}", line).ok();
+        }
+    }
+}
+
+/// PageRank damping factor and iterations, plenty to converge on crawls of a
+/// few hundred thousand groups.
+const DAMPING: f64 = 0.85;
+const ITERATIONS: usize = 30;
+
+/// PageRank over the undirected relationship graph, so groups allied with
+/// well connected groups rank above ones allied with many stragglers.
+fn page_rank(neighbours: &HashMap<u32, HashSet<u32>>) -> HashMap<u32, f64> {
+    let count = neighbours.len() as f64;
+    let mut ranks: HashMap<u32, f64> = neighbours.keys().map(|&id| (id, 1. / count)).collect();
+
+    for _ in 0..ITERATIONS {
+        let mut next: HashMap<u32, f64> = neighbours
+            .keys()
+            .map(|&id| (id, (1. - DAMPING) / count))
+            .collect();
+
+        for (id, linked) in neighbours.iter() {
+            let share = DAMPING * ranks[id] / linked.len() as f64;
+
+            for other in linked {
+                *next.entry(*other).or_default() += share;
+            }
+        }
+
+        ranks = next;
+    }
+
+    ranks
+}
+
+/// Ranks unclaimed groups in the `--graph` crawl by how many groups they're
+/// allied or at war with, and by PageRank to break ties.
+pub fn analyze_graph(
+    args: &Args,
+    graph_args: &GraphArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let path = args
+        .graph
+        .as_ref()
+        .ok_or("--graph has to point to a file recorded during a crawl")?;
+
+    let mut nodes: HashMap<u32, Node> = HashMap::new();
+    let mut neighbours: HashMap<u32, HashSet<u32>> = HashMap::new();
+    let mut allies: HashMap<u32, HashSet<u32>> = HashMap::new();
+
+    for line in fs::read_to_string(path)?.lines() {
+        let Ok(edge) = serde_json::from_str::<Edge>(line) else {
+            continue;
+        };
+
+        let (group, related) = (edge.group.id, edge.related.id);
+
+        if group == related {
+            continue;
+        }
+
+        neighbours.entry(group).or_default().insert(related);
+        neighbours.entry(related).or_default().insert(group);
+
+        if edge.relationship == "allies" {
+            allies.entry(group).or_default().insert(related);
+            allies.entry(related).or_default().insert(group);
+        }
+
+        // The latest sighting wins, ownership may have changed between crawls.
+        nodes.insert(group, edge.group);
+        nodes.insert(related, edge.related);
+    }
+
+    let ranks = page_rank(&neighbours);
+
+    let mut unclaimed: Vec<(&Node, usize, usize, f64)> = nodes
+        .values()
+        .filter(|node| !node.owned)
+        .map(|node| {
+            (
+                node,
+                neighbours.get(&node.id).map_or(0, HashSet::len),
+                allies.get(&node.id).map_or(0, HashSet::len),
+                ranks.get(&node.id).copied().unwrap_or(0.),
+            )
+        })
+        .filter(|(_, degree, _, _)| *degree >= graph_args.min_degree)
+        .collect();
+
+    unclaimed.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.3.total_cmp(&a.3))
+            .then(a.0.id.cmp(&b.0.id))
+    });
+    unclaimed.truncate(graph_args.top);
+
+    if graph_args.csv {
+        println!("id,name,members,degree,allies,pagerank");
+
+        for (node, degree, allies, rank) in unclaimed {
+            println!(
+                "{},\"{}\",{},{},{},{:.8}",
+                node.id,
+                node.name.replace('"', "\"\""),
+                node.member_count,
+                degree,
+                allies,
+                rank
+            );
+        }
+
+        return Ok(());
+    }
+
+    println!(
+        "{} groups, {} unclaimed",
+        nodes.len(),
+        nodes.values().filter(|node| !node.owned).count()
+    );
+
+    let separator = "│".truecolor(140, 140, 140);
+
+    println!(
+        "{:<50} {separator} {:<8} {separator} {:>7} {separator} {:>6} {separator} {:>6} {separator} {:>8}",
+        "Group", "Id", "Members", "Degree", "Allies", "Rank"
+    );
+
+    for (node, degree, allies, rank) in unclaimed {
+        println!(
+            "{:<50} {separator} {:<8} {separator} {:>7} {separator} {:>6} {separator} {:>6} {separator} {:>8}",
+            node.name.bold(),
+            node.id,
+            node.member_count,
+            degree.to_string().green(),
+            allies,
+            format!("{:.1}", rank * nodes.len() as f64)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use colored::Colorize;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response, ResponseBuilderExt, Url};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::{decorate, logfile};
+
+/// Set by `--debug-http`.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// How much of a body is logged before it's cut off.
+const MAX_BODY: usize = 1024;
+
+/// Headers carrying credentials, logged with their values replaced.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+    "x-api-key",
+    "x-csrf-token",
+];
+
+/// Query parameters whose name contains any of these are redacted.
+const SENSITIVE_PARAMS: &[&str] = &[
+    "auth",
+    "cookie",
+    "key",
+    "password",
+    "secret",
+    "session",
+    "signature",
+    "token",
+];
+
+const REDACTED: &str = "REDACTED";
+
+pub fn init(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Sends a request through the configured decorators, logging it and its
+/// response with credentials redacted when `--debug-http` is on.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let (client, request) = request.build_split();
+    let mut request = request?;
+
+    decorate::apply(&mut request);
+
+    if !ENABLED.load(Ordering::Relaxed) {
+        return client.execute(request).await;
+    }
+
+    let method = request.method().clone();
+    let url = redact_url(request.url());
+    let original_url = request.url().to_string();
+
+    log(&format!(
+        "--> {} {}{}{}",
+        method,
+        url,
+        format_headers(request.headers()),
+        request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(format_body)
+            .unwrap_or_default()
+    ));
+
+    let started = Instant::now();
+
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(err) => {
+            log(&format!(
+                "<-- {} {} failed after {}ms: {}",
+                method,
+                url,
+                started.elapsed().as_millis(),
+                err.to_string().replace(&original_url, &url)
+            ));
+            return Err(err);
+        }
+    };
+
+    let status = response.status();
+    let version = response.version();
+    let response_url = response.url().clone();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+
+    log(&format!(
+        "<-- {} {} {} ({}ms){}{}",
+        status,
+        method,
+        url,
+        started.elapsed().as_millis(),
+        format_headers(&headers),
+        format_body(&body)
+    ));
+
+    // The body was read for logging, so the response is rebuilt around it.
+    let mut rebuilt = http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(response_url)
+        .body(body)
+        .expect("the parts of a received response are valid");
+    *rebuilt.headers_mut() = headers;
+
+    Ok(rebuilt.into())
+}
+
+fn log(message: &str) {
+    eprintln!("{}", message.truecolor(140, 140, 140));
+    logfile::log("HTTP", message);
+}
+
+/// Drops passwords and secret query values, and the token part of webhook
+/// urls, which is all it takes to post to them.
+fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+
+    if url.password().is_some() {
+        url.set_password(Some(REDACTED)).ok();
+    }
+
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let lowercase = name.to_lowercase();
+
+                if SENSITIVE_PARAMS
+                    .iter()
+                    .any(|sensitive| lowercase.contains(sensitive))
+                {
+                    (name.into_owned(), REDACTED.to_string())
+                } else {
+                    (name.into_owned(), value.into_owned())
+                }
+            })
+            .collect();
+
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    let segments: Option<Vec<String>> = url
+        .path_segments()
+        .map(|segments| segments.map(str::to_string).collect());
+
+    if let Some(segments) = segments {
+        // Discord's /api/webhooks/<id>/<token> and Slack's /services/<ids>/<token>.
+        if let Some(position) = segments
+            .iter()
+            .position(|segment| segment == "webhooks" || segment == "services")
+        {
+            if segments.len() > position + 2 {
+                let mut redacted = segments[..position + 2].to_vec();
+                redacted.push(REDACTED.to_string());
+                url.set_path(&redacted.join("/"));
+            }
+        }
+    }
+
+    url.to_string()
+}
+
+fn format_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.into()
+            } else {
+                String::from_utf8_lossy(value.as_bytes())
+            };
+
+            format!("\n    {}: {}", name, value)
+        })
+        .collect()
+}
+
+fn format_body(body: &[u8]) -> String {
+    if body.is_empty() {
+        return String::new();
+    }
+
+    let text = String::from_utf8_lossy(&body[..body.len().min(MAX_BODY)]);
+    let text = redact_body(&text);
+
+    if body.len() > MAX_BODY {
+        format!("\n    {}… ({} bytes)", text, body.len())
+    } else {
+        format!("\n    {}", text)
+    }
+}
+
+/// Redacts `.ROBLOSECURITY` cookies and JSON fields that look like credentials.
+fn redact_body(body: &str) -> String {
+    static PATTERNS: OnceLock<(Regex, Regex)> = OnceLock::new();
+
+    let (cookie, field) = PATTERNS.get_or_init(|| {
+        (
+            Regex::new(r"_\|WARNING:-DO-NOT-SHARE-THIS\.[^\x22\s;]*").unwrap(),
+            Regex::new(
+                r#"(?i)("[^"]*(?:cookie|token|password|secret|api_?key|csrf)[^"]*"\s*:\s*)"[^"]*""#,
+            )
+            .unwrap(),
+        )
+    });
+
+    let body = cookie.replace_all(body, REDACTED);
+
+    field
+        .replace_all(&body, format!("${{1}}\"{}\"", REDACTED))
+        .into_owned()
+}
@@ -0,0 +1,516 @@
+use clap::ValueEnum;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::store::{self, Category};
+use crate::Args;
+
+/// Where the ids of groups to check come from when not searching. New
+/// scanning strategies only have to implement this and be added to
+/// [`Source`].
+pub trait IdSource: fmt::Debug {
+    /// The next id to check, `None` once the source has run dry.
+    fn next(&mut self) -> Option<u32>;
+}
+
+/// How many ids already in the store are drawn in a row before a pick is
+/// given up on.
+const MAX_REROLLS: u32 = 64;
+
+/// How often the store is checked for groups other scans have added, this
+/// scan's own picks are excluded as they're drawn.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What drawing from a [`Picker`] came up with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Draw {
+    Id(u32),
+    /// Every id drawn was already in the store.
+    Excluded,
+    /// The source ran dry.
+    OutOfIds,
+}
+
+/// Draws ids from a source, re-rolling ids that are already in the store so
+/// picks late in a scan aren't spent on ids that would be skipped anyway.
+#[derive(Debug)]
+pub struct Picker {
+    source: Box<dyn IdSource>,
+    store: PathBuf,
+    /// The ids in the store as of `modified` along with every id drawn since.
+    excluded: HashSet<u32>,
+    modified: Option<SystemTime>,
+    reloaded: Option<Instant>,
+    drawn: u64,
+    rerolled: u64,
+}
+
+impl Picker {
+    pub fn new(source: Box<dyn IdSource>, store: &Path) -> Self {
+        Self {
+            source,
+            store: store.to_path_buf(),
+            excluded: HashSet::new(),
+            modified: None,
+            reloaded: None,
+            drawn: 0,
+            rerolled: 0,
+        }
+    }
+
+    pub fn next(&mut self) -> Result<Draw, Box<dyn std::error::Error>> {
+        self.refresh()?;
+
+        for _ in 0..MAX_REROLLS {
+            let Some(group_id) = self.source.next() else {
+                return Ok(Draw::OutOfIds);
+            };
+
+            self.drawn += 1;
+
+            if self.excluded.insert(group_id) {
+                return Ok(Draw::Id(group_id));
+            }
+
+            self.rerolled += 1;
+        }
+
+        Ok(Draw::Excluded)
+    }
+
+    fn refresh(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self
+            .reloaded
+            .is_some_and(|reloaded| reloaded.elapsed() < RELOAD_INTERVAL)
+        {
+            return Ok(());
+        }
+
+        self.reloaded = Some(Instant::now());
+
+        let modified = fs::metadata(&self.store)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        if modified != self.modified {
+            self.excluded
+                .extend(store::load(&self.store)?.into_iter().map(|entry| entry.id));
+            self.modified = modified;
+        }
+
+        Ok(())
+    }
+
+    /// e.g. `Re-rolled 1200 of 2000 drawn ids already in the store (60.0%)`.
+    pub fn summary(&self) -> Option<String> {
+        if self.rerolled == 0 {
+            return None;
+        }
+
+        Some(format!(
+            "Re-rolled {} of {} drawn ids already in the store ({:.1}%)",
+            self.rerolled,
+            self.drawn,
+            self.rerolled as f64 / self.drawn as f64 * 100.
+        ))
+    }
+}
+
+/// A span of ids to scan and how often to pick from it relative to other
+/// spans, parsed from `--range start-end[:weight]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdRange {
+    pub start: u32,
+    pub end: u32,
+    pub weight: f64,
+}
+
+impl IdRange {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (span, weight) = match value.split_once(':') {
+            Some((span, weight)) => (
+                span,
+                weight
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|err| format!("invalid weight {weight}: {err}"))?,
+            ),
+            None => (value, 1.),
+        };
+
+        let (start, end) = span
+            .split_once('-')
+            .ok_or_else(|| String::from("range must be formatted as 'start-end[:weight]'"))?;
+        let start = start
+            .trim()
+            .parse::<u32>()
+            .map_err(|err| format!("invalid start {start}: {err}"))?;
+        let end = end
+            .trim()
+            .parse::<u32>()
+            .map_err(|err| format!("invalid end {end}: {err}"))?;
+
+        if start > end {
+            return Err(format!("range {start}-{end} ends before it starts"));
+        }
+
+        if !(weight > 0. && weight.is_finite()) {
+            return Err(format!("weight {weight} must be above 0"));
+        }
+
+        Ok(Self { start, end, weight })
+    }
+
+    fn len(&self) -> u64 {
+        self.end as u64 - self.start as u64 + 1
+    }
+}
+
+/// The ranges given with `--range`, or `--min` to `--max` if there are none.
+pub fn ranges(args: &Args) -> Vec<IdRange> {
+    if args.range.is_empty() {
+        vec![IdRange {
+            start: args.min,
+            end: args.max,
+            weight: 1.,
+        }]
+    } else {
+        args.range.clone()
+    }
+}
+
+/// A random id from one of `ranges`, picked in proportion to their weights.
+pub fn random_id(ranges: &[IdRange]) -> u32 {
+    let mut rng = rand::thread_rng();
+    let range = WeightedIndex::new(ranges.iter().map(|range| range.weight))
+        .map_or(ranges[0], |weights| ranges[weights.sample(&mut rng)]);
+
+    rng.gen_range(range.start..=range.end)
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Uniformly random ids in the scanned ranges
+    Random,
+    /// Every id in the scanned ranges once, in a random order
+    Permutation,
+    /// Every id in the scanned ranges in order
+    Sequential,
+    /// Random ids, favouring the ranges the store found the most groups in
+    Weighted,
+    /// The ids listed in --id-file, one per line
+    File,
+    /// Only the related groups left in --frontier
+    Frontier,
+}
+
+pub fn build(source: Source, args: &Args) -> Result<Box<dyn IdSource>, Box<dyn std::error::Error>> {
+    let ranges = ranges(args);
+
+    Ok(match source {
+        Source::Random => Box::new(Uniform { ranges }),
+        Source::Permutation => Box::new(Permutations(
+            ranges
+                .iter()
+                .map(|range| (range.weight, Permutation::new(range)))
+                .collect(),
+        )),
+        Source::Sequential => Box::new(Sequential {
+            next: ranges.first().map_or(0, |range| range.start as u64),
+            ranges: ranges.into(),
+        }),
+        Source::Weighted => Box::new(Weighted::new(ranges, &args.store)?),
+        Source::File => Box::new(IdFile::load(
+            args.id_file
+                .as_deref()
+                .ok_or("--id-source file needs --id-file")?,
+        )?),
+        Source::Frontier => Box::new(FrontierOnly),
+    })
+}
+
+#[derive(Debug)]
+struct Uniform {
+    ranges: Vec<IdRange>,
+}
+
+impl IdSource for Uniform {
+    fn next(&mut self) -> Option<u32> {
+        Some(random_id(&self.ranges))
+    }
+}
+
+/// Walks the range through `(a * i + c) mod n` with `a` coprime to `n`,
+/// which visits every id once without keeping track of them.
+#[derive(Debug)]
+struct Permutation {
+    min: u64,
+    len: u64,
+    multiplier: u64,
+    increment: u64,
+    index: u64,
+}
+
+impl Permutation {
+    fn new(range: &IdRange) -> Self {
+        let len = range.len();
+        let mut rng = rand::thread_rng();
+
+        let mut multiplier = rng.gen_range(1..=len);
+        while gcd(multiplier, len) != 1 {
+            multiplier = rng.gen_range(1..=len);
+        }
+
+        Self {
+            min: range.start as u64,
+            len,
+            multiplier,
+            increment: rng.gen_range(0..len),
+            index: 0,
+        }
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+impl IdSource for Permutation {
+    fn next(&mut self) -> Option<u32> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let offset = (self.multiplier as u128 * self.index as u128 + self.increment as u128)
+            % self.len as u128;
+        self.index += 1;
+
+        Some((self.min + offset as u64) as u32)
+    }
+}
+
+/// One permutation per range, taking turns in proportion to the weights
+/// until every one of them is walked through.
+#[derive(Debug)]
+struct Permutations(Vec<(f64, Permutation)>);
+
+impl IdSource for Permutations {
+    fn next(&mut self) -> Option<u32> {
+        while !self.0.is_empty() {
+            let index = WeightedIndex::new(self.0.iter().map(|(weight, _)| *weight))
+                .map_or(0, |weights| weights.sample(&mut rand::thread_rng()));
+
+            match self.0[index].1.next() {
+                Some(group_id) => return Some(group_id),
+                None => {
+                    self.0.remove(index);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Goes through the ranges in the order they were given.
+#[derive(Debug)]
+struct Sequential {
+    ranges: VecDeque<IdRange>,
+    next: u64,
+}
+
+impl IdSource for Sequential {
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            let range = self.ranges.front()?;
+
+            if self.next <= range.end as u64 {
+                self.next += 1;
+                return Some((self.next - 1) as u32);
+            }
+
+            self.ranges.pop_front();
+            self.next = self.ranges.front()?.start as u64;
+        }
+    }
+}
+
+/// How many ids each range the weighted source weighs spans.
+const WEIGHTED_BUCKET_SIZE: u64 = 100_000;
+
+/// How many ids the weighted source hands out before weighing the ranges
+/// again with what was found since.
+const WEIGHTED_RELOAD: u32 = 1000;
+
+/// A span of at most [`WEIGHTED_BUCKET_SIZE`] ids the weighted source weighs.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    start: u32,
+    end: u32,
+    /// Which of the ranges the bucket is part of.
+    range: usize,
+}
+
+#[derive(Debug)]
+struct Weighted {
+    ranges: Vec<IdRange>,
+    buckets: Vec<Bucket>,
+    store: PathBuf,
+    weights: Option<WeightedIndex<f64>>,
+    picks: u32,
+}
+
+impl Weighted {
+    fn new(ranges: Vec<IdRange>, store: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut buckets = vec![];
+
+        for (index, range) in ranges.iter().enumerate() {
+            let mut start = range.start as u64;
+
+            while start <= range.end as u64 {
+                let end = (start + WEIGHTED_BUCKET_SIZE - 1).min(range.end as u64);
+                buckets.push(Bucket {
+                    start: start as u32,
+                    end: end as u32,
+                    range: index,
+                });
+                start = end + 1;
+            }
+        }
+
+        let mut weighted = Self {
+            ranges,
+            buckets,
+            store: store.to_path_buf(),
+            weights: None,
+            picks: 0,
+        };
+
+        weighted.reload()?;
+
+        Ok(weighted)
+    }
+
+    /// Weighs each bucket by its share of available groups, smoothed so
+    /// buckets nothing was checked in yet still get picked. Each range keeps
+    /// the share of picks its weight gives it.
+    fn reload(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut counts = vec![(0u64, 0u64); self.buckets.len()];
+
+        for entry in store::load(&self.store)? {
+            if entry.category == Category::Nonexistent {
+                continue;
+            }
+
+            // Ranges may overlap, the first bucket an id falls in counts it.
+            let Some(index) = self
+                .buckets
+                .iter()
+                .position(|bucket| (bucket.start..=bucket.end).contains(&entry.id))
+            else {
+                continue;
+            };
+
+            let (checked, found) = &mut counts[index];
+            *checked += 1;
+
+            if entry.category == Category::FoundAvailable {
+                *found += 1;
+            }
+        }
+
+        let rates: Vec<f64> = counts
+            .iter()
+            .map(|(checked, found)| (*found as f64 + 1.) / (*checked as f64 + 2.))
+            .collect();
+
+        let mut range_totals = vec![0.; self.ranges.len()];
+        for (bucket, rate) in self.buckets.iter().zip(&rates) {
+            range_totals[bucket.range] += rate;
+        }
+
+        self.weights = WeightedIndex::new(self.buckets.iter().zip(&rates).map(|(bucket, rate)| {
+            self.ranges[bucket.range].weight * rate / range_totals[bucket.range]
+        }))
+        .ok();
+        self.picks = 0;
+
+        Ok(())
+    }
+}
+
+impl IdSource for Weighted {
+    fn next(&mut self) -> Option<u32> {
+        if self.picks >= WEIGHTED_RELOAD {
+            // A store that can't be read keeps the previous weights.
+            self.reload().ok();
+        }
+
+        self.picks += 1;
+
+        let mut rng = rand::thread_rng();
+        let bucket = self.buckets[self
+            .weights
+            .as_ref()
+            .map_or(0, |weights| weights.sample(&mut rng))];
+
+        Some(rng.gen_range(bucket.start..=bucket.end))
+    }
+}
+
+#[derive(Debug)]
+struct IdFile {
+    ids: VecDeque<u32>,
+}
+
+impl IdFile {
+    /// Reads ids one per line, skipping blank lines and `#` comments.
+    fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut ids = VecDeque::new();
+
+        for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            ids.push_back(line.parse().map_err(|err| {
+                format!(
+                    "{}:{}: invalid group id: {}",
+                    path.display(),
+                    index + 1,
+                    err
+                )
+            })?);
+        }
+
+        Ok(Self { ids })
+    }
+}
+
+impl IdSource for IdFile {
+    fn next(&mut self) -> Option<u32> {
+        self.ids.pop_front()
+    }
+}
+
+/// Hands out nothing, so scanning ends once the frontier, which is always
+/// taken from first, is empty.
+#[derive(Debug)]
+struct FrontierOnly;
+
+impl IdSource for FrontierOnly {
+    fn next(&mut self) -> Option<u32> {
+        None
+    }
+}
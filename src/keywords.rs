@@ -0,0 +1,86 @@
+/// Letters commonly swapped for look-alike digits in stylized names.
+const LEETSPEAK: [(char, char); 6] = [
+    ('a', '4'),
+    ('e', '3'),
+    ('i', '1'),
+    ('o', '0'),
+    ('s', '5'),
+    ('t', '7'),
+];
+
+/// Spellings that are commonly confused for one another.
+const MISSPELLINGS: [(&str, &str); 5] = [
+    ("ph", "f"),
+    ("ck", "k"),
+    ("ie", "ei"),
+    ("our", "or"),
+    ("z", "s"),
+];
+
+/// Derives variations of a keyword: plurals, common misspellings, leetspeak
+/// and spacing variants. The keyword itself comes first.
+pub fn expand_keyword(keyword: &str) -> Vec<String> {
+    let keyword = keyword.to_lowercase();
+    let mut variants: Vec<String> = vec![keyword.clone()];
+
+    let mut push = |variant: String| {
+        if !variant.is_empty() && !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    };
+
+    // Plurals.
+    if let Some(singular) = keyword.strip_suffix("es").filter(|_| keyword.len() > 4) {
+        push(singular.to_string());
+    }
+
+    if let Some(singular) = keyword.strip_suffix('s').filter(|_| keyword.len() > 3) {
+        push(singular.to_string());
+    } else {
+        push(format!("{}s", keyword));
+    }
+
+    // Misspellings: doubled letters collapsed, and confusable spellings
+    // swapped in both directions.
+    let mut collapsed = String::new();
+
+    for char in keyword.chars() {
+        if !collapsed.ends_with(char) || !char.is_alphabetic() {
+            collapsed.push(char);
+        }
+    }
+
+    push(collapsed);
+
+    for (from, to) in MISSPELLINGS {
+        if keyword.contains(from) {
+            push(keyword.replacen(from, to, 1));
+        } else if keyword.contains(to) && !keyword.contains(&to.repeat(2)) {
+            push(keyword.replacen(to, from, 1));
+        }
+    }
+
+    // Leetspeak.
+    push(
+        keyword
+            .chars()
+            .map(|char| {
+                LEETSPEAK
+                    .iter()
+                    .find(|(letter, _)| *letter == char)
+                    .map_or(char, |(_, digit)| *digit)
+            })
+            .collect(),
+    );
+
+    // Spacing variants.
+    if keyword.contains(' ') {
+        let words: Vec<&str> = keyword.split_whitespace().collect();
+
+        push(words.concat());
+        push(words.join("_"));
+        push(words.join("."));
+    }
+
+    variants
+}
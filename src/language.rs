@@ -0,0 +1,214 @@
+/// Common words that mostly appear in one language, for telling apart
+/// languages written in the Latin alphabet.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "are", "for", "with", "you", "your", "we", "our",
+            "this", "that", "join", "welcome", "be", "will", "all",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el",
+            "los",
+            "las",
+            "del",
+            "y",
+            "por",
+            "para",
+            "con",
+            "una",
+            "es",
+            "somos",
+            "bienvenidos",
+            "nuestro",
+            "únete",
+            "unete",
+            "grupo",
+            "que",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "os", "das", "dos", "não", "nao", "com", "um", "é", "você", "voce", "nosso", "bem",
+            "vindos", "seja", "ao", "grupo", "que", "para",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le",
+            "les",
+            "des",
+            "et",
+            "est",
+            "une",
+            "pour",
+            "dans",
+            "nous",
+            "vous",
+            "notre",
+            "bienvenue",
+            "avec",
+            "du",
+            "sur",
+            "pas",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der",
+            "die",
+            "das",
+            "und",
+            "ist",
+            "nicht",
+            "mit",
+            "wir",
+            "ein",
+            "eine",
+            "für",
+            "auf",
+            "willkommen",
+            "unsere",
+            "sie",
+            "zu",
+            "dem",
+            "den",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il",
+            "gli",
+            "della",
+            "di",
+            "è",
+            "siamo",
+            "benvenuti",
+            "gruppo",
+            "sono",
+            "questo",
+            "nel",
+            "che",
+            "non",
+            "per",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "het", "een", "van", "wij", "zijn", "niet", "welkom", "onze", "voor", "jullie", "en",
+            "de",
+        ],
+    ),
+    (
+        "tr",
+        &[
+            "ve", "bir", "bu", "için", "ile", "biz", "hoş", "geldiniz", "grubu", "değil", "çok",
+            "olan",
+        ],
+    ),
+    (
+        "id",
+        &[
+            "dan", "yang", "ini", "itu", "untuk", "dengan", "kami", "kita", "selamat", "datang",
+            "tidak", "ada",
+        ],
+    ),
+    (
+        "pl",
+        &[
+            "i", "w", "z", "na", "nie", "się", "jest", "witamy", "nasz", "grupa", "oraz", "dla",
+        ],
+    ),
+];
+
+/// Guesses the language of a text as an ISO 639-1 code, by its alphabet or,
+/// for the Latin alphabet, by which language's common words it uses most.
+/// `None` if there's too little to go on or it's a tie.
+pub fn detect(text: &str) -> Option<&'static str> {
+    if let Some(language) = detect_script(text) {
+        return Some(language);
+    }
+
+    let lowercase = text.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            (
+                *language,
+                words.iter().filter(|word| stopwords.contains(word)).count(),
+            )
+        })
+        .collect();
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    match scores.as_slice() {
+        [(language, best), (_, second), ..] if *best > 0 && best > second => Some(language),
+        _ => None,
+    }
+}
+
+/// The language of a text mostly written in an alphabet used by one
+/// language, such as Hangul for Korean.
+fn detect_script(text: &str) -> Option<&'static str> {
+    let mut letters = 0;
+    let mut counts: Vec<(&'static str, usize)> = vec![];
+    let mut kana = false;
+
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+
+        let language = match c as u32 {
+            0x0370..=0x03FF => "el",
+            0x0400..=0x04FF => "ru",
+            0x0590..=0x05FF => "he",
+            0x0600..=0x06FF => "ar",
+            0x0900..=0x097F => "hi",
+            0x0E00..=0x0E7F => "th",
+            0x1100..=0x11FF | 0xAC00..=0xD7AF => "ko",
+            0x3040..=0x30FF => {
+                kana = true;
+                "ja"
+            }
+            0x4E00..=0x9FFF => "zh",
+            _ => continue,
+        };
+
+        match counts.iter_mut().find(|(counted, _)| *counted == language) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((language, 1)),
+        }
+    }
+
+    // Japanese mixes kanji in with kana.
+    if kana {
+        let han = counts
+            .iter()
+            .find(|(language, _)| *language == "zh")
+            .map_or(0, |(_, count)| *count);
+
+        counts.retain(|(language, _)| *language != "zh");
+
+        if let Some((_, count)) = counts.iter_mut().find(|(language, _)| *language == "ja") {
+            *count += han;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .filter(|(_, count)| count * 2 > letters)
+        .map(|(language, _)| language)
+}
@@ -0,0 +1,118 @@
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::store::{self, Category};
+use crate::{Args, Group};
+
+/// How many of the best findings are kept across runs.
+const LEADERBOARD_SIZE: usize = 100;
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct StatsArgs {
+    /// How many of the best-scored groups ever found to list
+    #[arg(long, default_value_t = 20, env = "RECLAIMER_STATS_TOP")]
+    top: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    id: u32,
+    name: String,
+    score: u32,
+    member_count: u32,
+    found: String,
+}
+
+fn load(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Adds a finding to the leaderboard at `path`, replacing an earlier entry
+/// for the same group and keeping only the best scored.
+pub fn record(path: &Path, group: &Group, score: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let mut entries = load(path)?;
+
+    entries.retain(|entry| entry.id != group.id);
+    entries.push(Entry {
+        id: group.id,
+        name: group.name.clone(),
+        score,
+        member_count: group.member_count,
+        found: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+    });
+
+    // Stable, so earlier findings stay ahead of later ones with equal scores.
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    entries.truncate(LEADERBOARD_SIZE);
+
+    fs::write(path, serde_json::to_string(&entries)?)?;
+
+    Ok(())
+}
+
+/// Prints how many groups were checked and the best-scored groups ever found.
+pub fn stats(args: &Args, stats_args: &StatsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let stored = store::load(&args.store)?;
+
+    let mut categories: BTreeMap<Category, u64> = BTreeMap::new();
+    for entry in &stored {
+        *categories.entry(entry.category).or_default() += 1;
+    }
+
+    let nonexistent = categories
+        .remove(&Category::Nonexistent)
+        .unwrap_or_default();
+    let categories: Vec<String> = categories
+        .iter()
+        .map(|(category, count)| format!("{} {}", count, category))
+        .collect();
+
+    println!(
+        "{} groups checked{}, {} ids didn't exist",
+        stored.len() as u64 - nonexistent,
+        if categories.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", categories.join(", "))
+        },
+        nonexistent
+    );
+
+    let entries = load(&args.leaderboard)?;
+
+    if entries.is_empty() {
+        println!("{}", "Nothing found yet".yellow());
+        return Ok(());
+    }
+
+    let separator = "│".truecolor(140, 140, 140);
+
+    println!(
+        "{:>3} {separator} {:<50} {separator} {:<8} {separator} {:>7} {separator} {:>5} {separator} Found",
+        "#", "Group", "Id", "Members", "Score"
+    );
+
+    for (rank, entry) in entries.iter().take(stats_args.top).enumerate() {
+        println!(
+            "{:>3} {separator} {:<50} {separator} {:<8} {separator} {:>7} {separator} {:>5} {separator} {}",
+            rank + 1,
+            entry.name.blue(),
+            entry.id,
+            entry.member_count,
+            entry.score.to_string().yellow(),
+            entry.found
+        );
+    }
+
+    Ok(())
+}
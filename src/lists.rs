@@ -0,0 +1,49 @@
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+
+/// Group ids and name patterns read from an allow- or denylist file, one per
+/// line. Lines of only digits are ids, anything else is a regex matched
+/// against group names. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone)]
+pub struct GroupList {
+    ids: HashSet<u32>,
+    patterns: Vec<Regex>,
+}
+
+impl GroupList {
+    /// Parses the list at `path`, for use as a clap value parser.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| format!("failed to read {}: {}", path, err))?;
+
+        let mut list = GroupList {
+            ids: HashSet::new(),
+            patterns: vec![],
+        };
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Ok(id) = line.parse::<u32>() {
+                list.ids.insert(id);
+                continue;
+            }
+
+            list.patterns.push(
+                Regex::new(line)
+                    .map_err(|err| format!("{}:{}: invalid pattern: {}", path, index + 1, err))?,
+            );
+        }
+
+        Ok(list)
+    }
+
+    pub fn matches(&self, id: u32, name: &str) -> bool {
+        self.ids.contains(&id) || self.patterns.iter().any(|pattern| pattern.is_match(name))
+    }
+}
@@ -0,0 +1,52 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Keeps a second scan from using the same store at the same time by holding
+/// an exclusive lock on `<store>.lock` until dropped. The operating system
+/// releases it however the process exits, so a crash never leaves the store
+/// locked.
+#[derive(Debug)]
+pub struct StoreLock {
+    _file: File,
+}
+
+impl StoreLock {
+    pub fn acquire(store: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = PathBuf::from(format!("{}.lock", store.display()));
+
+        // The file is never removed, a scan could otherwise lock a file that
+        // was just unlinked while another one creates and locks a new one.
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut pid = String::new();
+                file.read_to_string(&mut pid).ok();
+
+                return Err(match pid.trim().parse::<u32>() {
+                    Ok(pid) => format!(
+                        "{} is in use by another scan (pid {})",
+                        store.display(),
+                        pid
+                    ),
+                    Err(_) => format!("{} is in use by another scan", store.display()),
+                }
+                .into());
+            }
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        }
+
+        // Only ever read to name the holder in the error above.
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+
+        Ok(Self { _file: file })
+    }
+}
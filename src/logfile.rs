@@ -0,0 +1,124 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The log file findings and errors are appended to, if one is configured.
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    opened: Instant,
+    max_size: u64,
+    max_age: Option<Duration>,
+    keep: usize,
+}
+
+impl LogFile {
+    fn open(path: &Path) -> std::io::Result<(File, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok((file, size))
+    }
+
+    fn should_rotate(&self) -> bool {
+        self.size >= self.max_size
+            || self
+                .max_age
+                .is_some_and(|max_age| self.opened.elapsed() >= max_age)
+    }
+
+    /// Shifts `path.1` to `path.2` and so on, dropping the oldest, then
+    /// starts a fresh file at `path`.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let rotated = |index: usize| PathBuf::from(format!("{}.{}", self.path.display(), index));
+
+        if self.keep == 0 {
+            fs::remove_file(&self.path).ok();
+        } else {
+            fs::remove_file(rotated(self.keep)).ok();
+
+            for index in (1..self.keep).rev() {
+                fs::rename(rotated(index), rotated(index + 1)).ok();
+            }
+
+            fs::rename(&self.path, rotated(1))?;
+        }
+
+        (self.file, self.size) = Self::open(&self.path)?;
+        self.opened = Instant::now();
+
+        Ok(())
+    }
+}
+
+/// Starts appending to the log file at `path`, rotating it once it grows past
+/// `max_size` bytes or, if set, has been written to for longer than `max_age`.
+pub fn init(
+    path: &Path,
+    max_size: u64,
+    max_age: Option<Duration>,
+    keep: usize,
+) -> std::io::Result<()> {
+    let (file, size) = LogFile::open(path)?;
+
+    *LOG_FILE.lock().unwrap() = Some(LogFile {
+        path: path.to_path_buf(),
+        file,
+        size,
+        opened: Instant::now(),
+        max_size,
+        max_age,
+        keep,
+    });
+
+    Ok(())
+}
+
+/// Appends a timestamped line to the log file, if one is configured.
+pub fn log(level: &str, message: &str) {
+    let mut log_file = LOG_FILE.lock().unwrap();
+
+    let Some(log_file) = log_file.as_mut() else {
+        return;
+    };
+
+    if log_file.should_rotate() {
+        log_file.rotate().ok();
+    }
+
+    let line = format!(
+        "{} {:<5} {}\n",
+        humantime::format_rfc3339_seconds(SystemTime::now()),
+        level,
+        message
+    );
+
+    if log_file.file.write_all(line.as_bytes()).is_ok() {
+        log_file.size += line.len() as u64;
+    }
+}
+
+/// Parses sizes such as `512`, `64K`, `10M` or `1G` into bytes.
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim().to_ascii_uppercase();
+    let value = value.trim_end_matches("IB").trim_end_matches('B');
+
+    let (number, multiplier) = match value.chars().last() {
+        Some('K') => (&value[..value.len() - 1], 1024),
+        Some('M') => (&value[..value.len() - 1], 1024 * 1024),
+        Some('G') => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+
+    number
+        .trim()
+        .parse::<u64>()
+        .map_err(|err| format!("invalid size {value}: {err}"))?
+        .checked_mul(multiplier)
+        .ok_or_else(|| "size too large".to_string())
+}
@@ -0,0 +1,89 @@
+use std::ops::RangeInclusive;
+
+/// Expands a charset specification such as `a-z0-9_` into its characters.
+pub fn expand_charset(charset: &str) -> Vec<char> {
+    let chars: Vec<char> = charset.chars().collect();
+    let mut expanded: Vec<char> = vec![];
+    let mut index = 0;
+
+    while index < chars.len() {
+        if index + 2 < chars.len() && chars[index + 1] == '-' {
+            expanded.extend(chars[index]..=chars[index + 2]);
+            index += 3;
+        } else {
+            expanded.push(chars[index]);
+            index += 1;
+        }
+    }
+
+    let mut unique: Vec<char> = vec![];
+
+    for char in expanded {
+        if !unique.contains(&char) {
+            unique.push(char);
+        }
+    }
+
+    unique
+}
+
+/// Enumerates every name of the given lengths made out of the charset,
+/// shortest names first.
+pub struct NameGenerator {
+    charset: Vec<char>,
+    lengths: RangeInclusive<usize>,
+    indices: Vec<usize>,
+    exhausted: bool,
+}
+
+impl NameGenerator {
+    pub fn new(charset: Vec<char>, lengths: RangeInclusive<usize>) -> Self {
+        Self {
+            exhausted: charset.is_empty() || lengths.is_empty(),
+            indices: vec![0; *lengths.start()],
+            charset,
+            lengths,
+        }
+    }
+}
+
+impl Iterator for NameGenerator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.exhausted {
+            return None;
+        }
+
+        let name: String = self
+            .indices
+            .iter()
+            .map(|index| self.charset[*index])
+            .collect();
+
+        let mut position = self.indices.len();
+
+        loop {
+            if position == 0 {
+                if self.indices.len() < *self.lengths.end() {
+                    self.indices = vec![0; self.indices.len() + 1];
+                } else {
+                    self.exhausted = true;
+                }
+
+                break;
+            }
+
+            position -= 1;
+            self.indices[position] += 1;
+
+            if self.indices[position] < self.charset.len() {
+                break;
+            }
+
+            self.indices[position] = 0;
+        }
+
+        Some(name)
+    }
+}
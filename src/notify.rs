@@ -0,0 +1,143 @@
+use reqwest::Client;
+use std::time::{Duration, Instant};
+
+use crate::sink::Sink;
+use crate::{Args, Group};
+
+/// Discord rejects messages whose content is longer than this.
+const MAX_CONTENT_LENGTH: usize = 2000;
+
+/// How many groups are listed in a digest before the rest are summarized.
+const DIGEST_TOP_GROUPS: usize = 10;
+
+/// Sends findings to a Discord-compatible webhook, either one message per
+/// group or batched into a digest every interval.
+#[derive(Debug)]
+pub struct Notifier {
+    sink: Option<Sink>,
+    digest: Option<Duration>,
+    pending: Vec<(Group, u32)>,
+    last_digest: Instant,
+}
+
+impl Notifier {
+    pub fn new(args: &Args, client: &Client) -> Self {
+        Self {
+            sink: args.webhook.clone().map(|webhook| {
+                Sink::new(
+                    webhook,
+                    client.clone(),
+                    args.notify_queue_size,
+                    args.notify_overflow,
+                    args.notify_outbox.clone(),
+                )
+            }),
+            digest: args.notify_digest,
+            pending: vec![],
+            last_digest: Instant::now(),
+        }
+    }
+
+    pub async fn notify(
+        &mut self,
+        group: &Group,
+        score: u32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.sink.is_none() {
+            return Ok(());
+        }
+
+        if self.digest.is_some() {
+            self.pending.push((group.clone(), score));
+            return self.flush().await;
+        }
+
+        self.send(format_group(group, score)).await
+    }
+
+    /// Sends the pending digest if its interval has elapsed.
+    pub async fn flush(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.digest {
+            Some(digest) if self.last_digest.elapsed() >= digest => self.send_digest().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Sends the pending digest regardless of its interval, then waits for
+    /// every queued message to be delivered.
+    pub async fn finish(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let result = self.send_digest().await;
+
+        if let Some(sink) = self.sink.as_mut() {
+            sink.close().await;
+        }
+
+        result
+    }
+
+    /// How many messages are waiting to be delivered and for how long.
+    pub fn backlog(&self) -> Option<(usize, Duration)> {
+        self.sink.as_ref().map(|sink| sink.backlog())
+    }
+
+    async fn send_digest(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.last_digest = Instant::now();
+
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut groups = std::mem::take(&mut self.pending);
+        groups.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+        let mut content = format!(
+            "**{}** unclaimed group{} found in the last {}",
+            groups.len(),
+            if groups.len() == 1 { "" } else { "s" },
+            humantime::format_duration(self.digest.unwrap_or_default())
+        );
+
+        for (group, score) in groups.iter().take(DIGEST_TOP_GROUPS) {
+            content.push('\n');
+            content.push_str(&format_group(group, *score));
+        }
+
+        if groups.len() > DIGEST_TOP_GROUPS {
+            content.push_str(&format!("\n…and {} more", groups.len() - DIGEST_TOP_GROUPS));
+        }
+
+        self.send(content).await
+    }
+
+    async fn send(&self, content: String) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(sink) = &self.sink else {
+            return Ok(());
+        };
+
+        sink.push(content.chars().take(MAX_CONTENT_LENGTH).collect())
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn format_group(group: &Group, score: u32) -> String {
+    format!(
+        "[{}](<https://www.roblox.com/groups/{}>){} • {} • {} • {} members • score {}",
+        group.name,
+        group.id,
+        group
+            .previous_name
+            .as_ref()
+            .map(|previous_name| format!(" (formerly {})", previous_name))
+            .unwrap_or_default(),
+        group.id,
+        if group.public_entry_allowed {
+            "Open"
+        } else {
+            "Closed"
+        },
+        group.member_count,
+        score
+    )
+}
@@ -0,0 +1,45 @@
+use colored::{ColoredString, Colorize};
+use std::sync::atomic::{AtomicI8, Ordering};
+
+/// -1 with `--quiet`, otherwise how many times `-v` was given.
+static VERBOSITY: AtomicI8 = AtomicI8::new(0);
+
+/// `-v`: why checked groups were skipped.
+pub const SKIPS: i8 = 1;
+/// `-vv`: every checked group.
+pub const CHECKS: i8 = 2;
+/// `-vvv`: retry, failover and backoff decisions.
+pub const RETRIES: i8 = 3;
+
+pub fn init(verbose: u8, quiet: bool) {
+    VERBOSITY.store(
+        if quiet {
+            -1
+        } else {
+            verbose.min(RETRIES as u8) as i8
+        },
+        Ordering::Relaxed,
+    );
+}
+
+pub fn is_quiet() -> bool {
+    VERBOSITY.load(Ordering::Relaxed) < 0
+}
+
+/// Prints progress and warnings, unless `--quiet`.
+pub fn info(message: ColoredString) {
+    if !is_quiet() {
+        println!("{}", message);
+    }
+}
+
+pub fn is_enabled(level: i8) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level
+}
+
+/// Prints diagnostics shown from the given verbosity level up.
+pub fn verbose(level: i8, message: &str) {
+    if is_enabled(level) {
+        println!("{}", message.truecolor(140, 140, 140));
+    }
+}
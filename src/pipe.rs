@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::record::FindingRecord;
+
+/// How many findings are held while no reader has the pipe open, dropping
+/// the oldest beyond that.
+const MAX_BACKLOG: usize = 1000;
+
+/// Streams findings as NDJSON into a named pipe for another process to
+/// consume as they happen. Findings made while nothing reads the pipe are
+/// held and written once a reader opens it, so the scan never waits on one.
+#[derive(Debug)]
+pub struct FindingPipe {
+    path: PathBuf,
+    writer: Option<File>,
+    backlog: VecDeque<String>,
+    /// How much of the first line in the backlog the reader already has.
+    written: usize,
+}
+
+impl FindingPipe {
+    /// Creates the FIFO at `path` unless one is already there.
+    pub fn create(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        fifo::make(path)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: None,
+            backlog: VecDeque::new(),
+            written: 0,
+        })
+    }
+
+    pub fn send(&mut self, finding: &FindingRecord) -> Result<(), Box<dyn std::error::Error>> {
+        if self.backlog.len() >= MAX_BACKLOG {
+            // A line the reader has part of has to be finished first.
+            self.backlog.remove(usize::from(self.written > 0));
+        }
+
+        self.backlog
+            .push_back(format!("{}\n", serde_json::to_string(finding)?));
+
+        self.flush()
+    }
+
+    /// Writes as much of the backlog as the pipe takes without blocking, if a
+    /// reader has it open. The rest is written on a later flush. A reader
+    /// going away drops the connection, the line it was written keeps its
+    /// place and is written whole to the next reader.
+    pub fn flush(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.backlog.is_empty() {
+            return Ok(());
+        }
+
+        if self.writer.is_none() {
+            self.writer = fifo::open_writer(&self.path)?;
+        }
+
+        let Some(writer) = &mut self.writer else {
+            return Ok(());
+        };
+
+        while let Some(line) = self.backlog.front() {
+            match writer.write(&line.as_bytes()[self.written..]) {
+                Ok(0) => return Ok(()),
+                Ok(written) => {
+                    self.written += written;
+
+                    if self.written == line.len() {
+                        self.backlog.pop_front();
+                        self.written = 0;
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.writer = None;
+                    self.written = 0;
+
+                    return match err.kind() {
+                        ErrorKind::BrokenPipe => Ok(()),
+                        _ => Err(err.into()),
+                    };
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+mod fifo {
+    use std::ffi::CString;
+    use std::fs::{File, OpenOptions};
+    use std::io::ErrorKind;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+    use std::path::Path;
+
+    pub fn make(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        match path.metadata() {
+            Ok(metadata) if metadata.file_type().is_fifo() => return Ok(()),
+            Ok(_) => return Err(format!("{} exists and isn't a named pipe", path.display()).into()),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let c_path = CString::new(path.as_os_str().as_bytes())?;
+
+        // SAFETY: c_path is a valid NUL-terminated string for the duration of the call.
+        if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        Ok(())
+    }
+
+    /// Opens the pipe for writing without waiting for a reader, `None` if
+    /// there's no reader yet. Writes stay non-blocking, a full pipe fails
+    /// them with `WouldBlock` instead of stalling the scan.
+    pub fn open_writer(path: &Path) -> Result<Option<File>, Box<dyn std::error::Error>> {
+        match OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)
+        {
+            Ok(file) => Ok(Some(file)),
+            Err(err) if err.raw_os_error() == Some(libc::ENXIO) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod fifo {
+    use std::fs::File;
+    use std::path::Path;
+
+    pub fn make(_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        Err("--pipe needs named pipes, which are only supported on Unix".into())
+    }
+
+    pub fn open_writer(_path: &Path) -> Result<Option<File>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+}
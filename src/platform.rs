@@ -0,0 +1,99 @@
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use terminal_link::Link;
+
+/// Set once Ctrl-C has been pressed, scanning stops after the current group.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+static HYPERLINKS: OnceLock<bool> = OnceLock::new();
+
+/// Whether group names and social links are printed as clickable links.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hyperlinks {
+    /// Only when the terminal is known to support them
+    Auto,
+    Always,
+    Never,
+}
+
+/// Prepares the console for colors, hyperlinks and the live status line.
+pub fn init_console(hyperlinks: Hyperlinks) {
+    // stdout and stderr share the console's screen buffer, so this enables
+    // escape sequences for the status line on stderr too.
+    #[cfg(windows)]
+    colored::control::set_virtual_terminal(true).ok();
+
+    HYPERLINKS
+        .set(match hyperlinks {
+            Hyperlinks::Auto => supports_hyperlinks(),
+            Hyperlinks::Always => true,
+            Hyperlinks::Never => false,
+        })
+        .ok();
+}
+
+/// The legacy Windows console prints OSC 8 sequences as garbage, unlike
+/// Windows Terminal and virtually every terminal emulator elsewhere.
+fn supports_hyperlinks() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return false;
+    }
+
+    if cfg!(windows) {
+        return std::env::var_os("WT_SESSION").is_some()
+            || std::env::var_os("TERM_PROGRAM").is_some();
+    }
+
+    true
+}
+
+/// `text` linking to `url`, or just `text` if hyperlinks are disabled.
+pub fn link(text: &str, url: &str) -> String {
+    if *HYPERLINKS.get().unwrap_or(&false) {
+        Link::new(text, url).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Stops scanning gracefully on the first Ctrl-C and exits immediately on
+/// the second.
+pub fn handle_ctrl_c(message: &'static str, exit_code: i32) {
+    tokio::spawn(async move {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if INTERRUPTED.swap(true, Ordering::Relaxed) {
+                std::process::exit(exit_code);
+            }
+
+            eprintln!("\n{}", message);
+        }
+    });
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+/// Where settings and data live when no path is given: the working directory,
+/// or `%APPDATA%\rbx-reclaimer` on Windows where binaries are usually started
+/// from Explorer or the Downloads folder.
+pub fn data_dir() -> PathBuf {
+    if cfg!(windows) {
+        if let Some(app_data) = std::env::var_os("APPDATA") {
+            return PathBuf::from(app_data).join("rbx-reclaimer");
+        }
+    }
+
+    PathBuf::new()
+}
+
+pub fn default_store() -> PathBuf {
+    data_dir().join("groups.json")
+}
@@ -0,0 +1,226 @@
+use colored::Colorize;
+use reqwest::{Client, Url};
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use crate::systemd;
+
+/// How a request sent through a proxy turned out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    RateLimited,
+    Error,
+}
+
+/// Request outcome counters for a single proxy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProxyHealth {
+    pub requests: u64,
+    pub errors: u64,
+    pub rate_limited: u64,
+    pub quarantines: u64,
+    consecutive_failures: u32,
+    quarantined_until: Option<Instant>,
+    last_request: Option<Instant>,
+}
+
+impl ProxyHealth {
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.;
+        }
+
+        (self.errors + self.rate_limited) as f64 / self.requests as f64
+    }
+
+    fn is_quarantined(&self, now: Instant) -> bool {
+        self.quarantined_until.is_some_and(|until| until > now)
+    }
+}
+
+struct PoolEntry {
+    label: String,
+    client: Client,
+    health: ProxyHealth,
+}
+
+/// Rotates requests across one client per proxy, rate limiting each proxy on
+/// its own and temporarily taking proxies that keep failing out of rotation.
+pub struct ProxyPool {
+    entries: RefCell<Vec<PoolEntry>>,
+    next: Cell<usize>,
+    min_interval: Option<Duration>,
+    max_failures: u32,
+    quarantine: Duration,
+}
+
+impl ProxyPool {
+    /// Pool over labelled clients, or over just the direct client when no
+    /// proxies or local addresses are configured.
+    pub fn new(
+        direct: Client,
+        proxied: Vec<(String, Client)>,
+        rps: Option<f64>,
+        max_failures: u32,
+        quarantine: Duration,
+    ) -> Self {
+        let entries = if proxied.is_empty() {
+            vec![PoolEntry {
+                label: String::from("direct"),
+                client: direct,
+                health: ProxyHealth::default(),
+            }]
+        } else {
+            proxied
+                .into_iter()
+                .map(|(label, client)| PoolEntry {
+                    label,
+                    client,
+                    health: ProxyHealth::default(),
+                })
+                .collect()
+        };
+
+        Self {
+            entries: RefCell::new(entries),
+            next: Cell::new(0),
+            min_interval: rps
+                .filter(|rps| *rps > 0.)
+                .map(|rps| Duration::from_secs_f64(1. / rps)),
+            max_failures,
+            quarantine,
+        }
+    }
+
+    /// The next proxy in the rotation that isn't quarantined, or the one
+    /// coming out of quarantine soonest if they all are.
+    pub fn pick(&self) -> usize {
+        let entries = self.entries.borrow();
+        let now = Instant::now();
+        let start = self.next.get();
+
+        let index = (0..entries.len())
+            .map(|offset| (start + offset) % entries.len())
+            .find(|index| !entries[*index].health.is_quarantined(now))
+            .unwrap_or_else(|| {
+                (0..entries.len())
+                    .min_by_key(|index| entries[*index].health.quarantined_until)
+                    .unwrap_or(0)
+            });
+
+        self.next.set(index + 1);
+
+        index
+    }
+
+    pub fn client(&self, index: usize) -> Client {
+        self.entries.borrow()[index].client.clone()
+    }
+
+    /// Waits until the proxy may be used again under `--proxy-rps`.
+    pub async fn wait(&self, index: usize) {
+        let last_request = self.entries.borrow()[index].health.last_request;
+
+        if let (Some(min_interval), Some(last_request)) = (self.min_interval, last_request) {
+            systemd::sleep(min_interval.saturating_sub(last_request.elapsed())).await;
+        }
+
+        self.entries.borrow_mut()[index].health.last_request = Some(Instant::now());
+    }
+
+    pub fn record(&self, index: usize, outcome: Outcome) {
+        let mut entries = self.entries.borrow_mut();
+        let pooled = entries.len() > 1;
+        let entry = &mut entries[index];
+        let health = &mut entry.health;
+
+        health.requests += 1;
+
+        match outcome {
+            Outcome::Success => {
+                health.consecutive_failures = 0;
+                return;
+            }
+            Outcome::RateLimited => health.rate_limited += 1,
+            Outcome::Error => health.errors += 1,
+        }
+
+        health.consecutive_failures += 1;
+
+        // Quarantining the only client would just stall every request.
+        if pooled && health.consecutive_failures >= self.max_failures {
+            health.consecutive_failures = 0;
+            health.quarantines += 1;
+            health.quarantined_until = Some(Instant::now() + self.quarantine);
+
+            let message = format!(
+                "Proxy {} failed {} times in a row, quarantining it for {}",
+                entry.label,
+                self.max_failures,
+                humantime::format_duration(self.quarantine)
+            );
+
+            crate::output::info(message.yellow());
+            crate::logfile::log("WARN", &message);
+        }
+    }
+
+    /// Labels and counters of every proxy, in the order they were configured.
+    pub fn health(&self) -> Vec<(String, ProxyHealth)> {
+        self.entries
+            .borrow()
+            .iter()
+            .map(|entry| (entry.label.clone(), entry.health))
+            .collect()
+    }
+
+    /// Prints a table of per-proxy request counts, error rates and
+    /// quarantines.
+    pub fn report(&self) {
+        let separator = "│".truecolor(140, 140, 140);
+
+        println!(
+            "{:<32} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>7} {separator} {:>11}",
+            "Proxy", "requests", "errors", "429s", "failed", "quarantines"
+        );
+
+        for (label, health) in self.health() {
+            let error_rate = format!("{:.1}%", health.error_rate() * 100.);
+
+            println!(
+                "{:<32} {separator} {:>8} {separator} {:>8} {separator} {:>8} {separator} {:>7} {separator} {:>11}",
+                label,
+                health.requests,
+                health.errors,
+                health.rate_limited,
+                if health.error_rate() >= 0.5 {
+                    error_rate.red()
+                } else {
+                    error_rate.normal()
+                },
+                health.quarantines
+            );
+
+            crate::logfile::log(
+                "INFO",
+                &format!(
+                    "Proxy {}: {} requests, {} errors, {} rate limited, {} quarantines",
+                    label, health.requests, health.errors, health.rate_limited, health.quarantines
+                ),
+            );
+        }
+    }
+}
+
+/// The proxy url without its credentials, safe to print and log.
+pub fn redact(proxy: &str) -> String {
+    match Url::parse(proxy) {
+        Ok(mut url) => {
+            url.set_username("").ok();
+            url.set_password(None).ok();
+            url.to_string().trim_end_matches('/').to_string()
+        }
+        Err(_) => String::from("<invalid proxy>"),
+    }
+}
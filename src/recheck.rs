@@ -0,0 +1,105 @@
+use rand::Rng;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::store::{self, Category};
+
+/// How often the store is looked through again for groups that came due
+/// once every due group was rechecked.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Parses `--recheck` values such as `owned=30d`.
+pub fn parse_ttl(value: &str) -> Result<(Category, Duration), String> {
+    let (category, ttl) = value
+        .split_once('=')
+        .ok_or_else(|| String::from("recheck must be formatted as 'category=age'"))?;
+
+    Ok((
+        category.trim().parse()?,
+        humantime::parse_duration(ttl.trim()).map_err(|err| err.to_string())?,
+    ))
+}
+
+/// Groups in the store that are old enough to be checked again, oldest
+/// first, handed out one at a time at a jittered pace so fresh scanning
+/// keeps going in between.
+#[derive(Debug)]
+pub struct Rechecks {
+    store: PathBuf,
+    ttls: Vec<(Category, Duration)>,
+    interval: Duration,
+    due: VecDeque<u32>,
+    next_at: Instant,
+    loaded_at: Option<Instant>,
+}
+
+impl Rechecks {
+    pub fn new(store: &Path, ttls: &[(Category, Duration)], interval: Duration) -> Self {
+        Self {
+            store: store.to_path_buf(),
+            ttls: ttls.to_vec(),
+            interval,
+            due: VecDeque::new(),
+            next_at: Instant::now(),
+            loaded_at: None,
+        }
+    }
+
+    /// The next group to recheck, if one is due and it's its turn.
+    pub fn next(&mut self) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+        if self.ttls.is_empty() || Instant::now() < self.next_at {
+            return Ok(None);
+        }
+
+        if self.due.is_empty()
+            && self
+                .loaded_at
+                .is_none_or(|loaded_at| loaded_at.elapsed() >= RELOAD_INTERVAL)
+        {
+            self.load()?;
+        }
+
+        let Some(group_id) = self.due.pop_front() else {
+            return Ok(None);
+        };
+
+        self.next_at = Instant::now()
+            + self
+                .interval
+                .mul_f64(rand::thread_rng().gen_range(0.5..1.5));
+
+        Ok(Some(group_id))
+    }
+
+    fn load(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let now = SystemTime::now();
+
+        // Entries from before timestamps were recorded sort first.
+        let mut due: Vec<(Option<SystemTime>, u32)> = store::load(&self.store)?
+            .into_iter()
+            .filter_map(|entry| {
+                let (_, ttl) = self
+                    .ttls
+                    .iter()
+                    .find(|(category, _)| *category == entry.category)?;
+                let at = entry
+                    .at
+                    .as_deref()
+                    .and_then(|at| humantime::parse_rfc3339(at).ok());
+
+                match at {
+                    Some(at) if at + *ttl > now => None,
+                    _ => Some((at, entry.id)),
+                }
+            })
+            .collect();
+
+        due.sort();
+
+        self.due = due.into_iter().map(|(_, group_id)| group_id).collect();
+        self.loaded_at = Some(Instant::now());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::SystemTime;
+
+use crate::{Group, SocialLink};
+
+/// Bumped whenever a field is renamed, removed or changes meaning. Adding
+/// fields doesn't need a bump, readers keep fields they don't know in `extra`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A finding as written to --output-dir and sent to --enrich-url.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FindingRecord {
+    pub schema_version: u32,
+    pub group: Group,
+    pub score: u32,
+    #[serde(default)]
+    pub social_links: Vec<SocialLink>,
+    #[serde(default)]
+    pub query: Option<String>,
+    pub found: String,
+    /// Fields added by later versions, kept so records survive a round trip.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl FindingRecord {
+    pub fn new(
+        group: &Group,
+        score: u32,
+        social_links: &[SocialLink],
+        query: Option<&str>,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            group: group.clone(),
+            score,
+            social_links: social_links.to_vec(),
+            query: query.map(str::to_string),
+            found: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            extra: Map::new(),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
+
+/// A `--resolve host:port:address` override, sending requests for the host to
+/// the address instead of looking it up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub host: String,
+    pub address: SocketAddr,
+}
+
+/// Parses curl's `host:port:address` format, with IPv6 addresses optionally
+/// in brackets. The port is kept for compatibility, but the address is used
+/// for the host on every port.
+pub fn parse_override(value: &str) -> Result<Override, String> {
+    let mut parts = value.splitn(3, ':');
+
+    let (Some(host), Some(port), Some(address)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(String::from(
+            "override must be formatted as 'host:port:address'",
+        ));
+    };
+
+    if host.is_empty() {
+        return Err(String::from("override is missing a host"));
+    }
+
+    let port = port
+        .parse::<u16>()
+        .map_err(|err| format!("invalid port {port}: {err}"))?;
+    let address = address
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .map_err(|err| format!("invalid address {address}: {err}"))?;
+
+    Ok(Override {
+        host: host.to_lowercase(),
+        address: SocketAddr::new(address, port),
+    })
+}
+
+/// Looks hosts up through a DNS-over-HTTPS resolver's JSON api, such as
+/// `https://cloudflare-dns.com/dns-query` or `https://dns.google/resolve`.
+pub struct DohResolver {
+    /// Resolves the DoH server itself through the system resolver.
+    client: Client,
+    url: Url,
+}
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    data: String,
+}
+
+/// Record types of IPv4 and IPv6 addresses.
+const A: u16 = 1;
+const AAAA: u16 = 28;
+
+impl DohResolver {
+    pub fn new(url: Url) -> Self {
+        Self {
+            client: Client::new(),
+            url,
+        }
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let host = name.as_str().to_string();
+
+        Box::pin(async move {
+            let mut addresses = vec![];
+
+            for record_type in [A, AAAA] {
+                addresses.extend(lookup(&client, &url, &host, record_type).await?);
+
+                if !addresses.is_empty() {
+                    break;
+                }
+            }
+
+            if addresses.is_empty() {
+                return Err(format!("{} has no addresses", host).into());
+            }
+
+            let addresses: Addrs = Box::new(
+                addresses
+                    .into_iter()
+                    .map(|address| SocketAddr::new(address, 0)),
+            );
+
+            Ok(addresses)
+        })
+    }
+}
+
+async fn lookup(
+    client: &Client,
+    url: &Url,
+    host: &str,
+    record_type: u16,
+) -> Result<Vec<IpAddr>, Box<dyn std::error::Error + Send + Sync>> {
+    let response: DohResponse = client
+        .get(url.clone())
+        .query(&[("name", host), ("type", &record_type.to_string())])
+        .header("Accept", "application/dns-json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // Anything but NOERROR, e.g. NXDOMAIN for a host that doesn't exist.
+    if response.status != 0 {
+        return Err(format!(
+            "looking up {} failed with DNS status {}",
+            host, response.status
+        )
+        .into());
+    }
+
+    Ok(response
+        .answer
+        .iter()
+        .filter(|answer| answer.record_type == record_type)
+        .filter_map(|answer| answer.data.parse().ok())
+        .collect())
+}
@@ -0,0 +1,103 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::pipe::FindingPipe;
+use crate::record::FindingRecord;
+
+/// Findings of this run as NDJSON in `--output-dir`, named after when the
+/// run started so scheduled runs never overwrite each other, and streamed
+/// into `--pipe`.
+#[derive(Debug)]
+pub struct RunOutput {
+    file: Option<File>,
+    pipe: Option<FindingPipe>,
+}
+
+impl RunOutput {
+    pub fn create(
+        dir: Option<&Path>,
+        pipe: Option<&Path>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let pipe = pipe.map(FindingPipe::create).transpose()?;
+
+        let Some(dir) = dir else {
+            return Ok(Self { file: None, pipe });
+        };
+
+        fs::create_dir_all(dir)?;
+
+        // 2024-01-02T03:04:05Z becomes 20240102T030405Z, safe on every filesystem.
+        let started = humantime::format_rfc3339_seconds(SystemTime::now())
+            .to_string()
+            .replace(['-', ':'], "");
+        let mut name = format!("run-{}.ndjson", started);
+        let mut attempt = 1;
+
+        // Runs started within the same second get numbered instead.
+        let file = loop {
+            match File::options()
+                .write(true)
+                .create_new(true)
+                .open(dir.join(&name))
+            {
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    attempt += 1;
+                    name = format!("run-{}-{}.ndjson", started, attempt);
+                }
+                result => break result?,
+            }
+        };
+
+        link_latest(dir, &name)?;
+
+        Ok(Self {
+            file: Some(file),
+            pipe,
+        })
+    }
+
+    pub fn record(&mut self, finding: &FindingRecord) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(pipe) = &mut self.pipe {
+            pipe.send(finding)?;
+        }
+
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{}", serde_json::to_string(finding)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Hands findings held back for want of a reader to one that showed up
+    /// since.
+    pub fn flush(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match &mut self.pipe {
+            Some(pipe) => pipe.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Points `<dir>/latest` at the run's file. The link is relative so the
+/// directory can be moved or mounted elsewhere.
+fn link_latest(dir: &Path, name: &str) -> std::io::Result<()> {
+    let latest: PathBuf = dir.join("latest");
+
+    if latest.symlink_metadata().is_ok() {
+        fs::remove_file(&latest)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(name, &latest)?;
+
+    // Symlinks need developer mode or admin rights on Windows, fall back to
+    // a file naming the latest run.
+    #[cfg(windows)]
+    if std::os::windows::fs::symlink_file(name, &latest).is_err() {
+        fs::write(&latest, name)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use rand::Rng;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::Group;
+
+/// How far a single query has been paged through.
+#[derive(Debug)]
+pub struct Keyword {
+    pub query: String,
+    pub next_page_cursor: Option<String>,
+    cooling_until: Option<Instant>,
+}
+
+impl Keyword {
+    /// Moves on to the page after the one just searched. Once the last page
+    /// is reached the keyword rests for `cooldown` before being searched from
+    /// the first page again.
+    pub fn advance(&mut self, next_page_cursor: Option<String>, cooldown: Duration) {
+        if next_page_cursor.is_none() {
+            self.cooling_until = Some(Instant::now() + cooldown);
+        }
+
+        self.next_page_cursor = next_page_cursor;
+    }
+
+    fn is_cooling(&self, now: Instant) -> bool {
+        self.cooling_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Rotates through the search queries one page at a time, so each query
+/// picks up where it left off instead of being searched from the first page
+/// every time a group is found.
+#[derive(Debug)]
+pub struct KeywordQueue {
+    keywords: VecDeque<Keyword>,
+    /// Available groups from the pages searched so far, along with the query
+    /// that surfaced them, waiting to be checked.
+    pending: Vec<(Group, String)>,
+}
+
+impl KeywordQueue {
+    pub fn new(queries: &[String]) -> Self {
+        Self {
+            keywords: queries
+                .iter()
+                .map(|query| Keyword {
+                    query: query.clone(),
+                    next_page_cursor: None,
+                    cooling_until: None,
+                })
+                .collect(),
+            pending: vec![],
+        }
+    }
+
+    /// The next keyword that isn't cooling down, moved to the back of the
+    /// queue. `None` once every keyword is cooling down.
+    pub fn next(&mut self) -> Option<&mut Keyword> {
+        let now = Instant::now();
+        let position = self
+            .keywords
+            .iter()
+            .position(|keyword| !keyword.is_cooling(now))?;
+
+        let keyword = self.keywords.remove(position)?;
+        self.keywords.push_back(keyword);
+
+        self.keywords.back_mut()
+    }
+
+    /// Lets every keyword be searched again right away.
+    pub fn reset_cooldowns(&mut self) {
+        for keyword in self.keywords.iter_mut() {
+            keyword.cooling_until = None;
+        }
+    }
+
+    /// Queues a group to be checked, unless another query surfaced it first.
+    pub fn push(&mut self, group: Group, query: &str) {
+        if !self
+            .pending
+            .iter()
+            .any(|(pending, _)| pending.id == group.id)
+        {
+            self.pending.push((group, query.to_string()));
+        }
+    }
+
+    /// Takes a random group out of the ones waiting to be checked.
+    pub fn take(&mut self) -> Option<(Group, String)> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        let index = rand::thread_rng().gen_range(0..self.pending.len());
+
+        Some(self.pending.swap_remove(index))
+    }
+}
@@ -0,0 +1,181 @@
+use clap::{Args as ClapArgs, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::record::SCHEMA_VERSION;
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct SchemaArgs {
+    /// Which record to print the schema of, all of them if not given
+    #[arg(value_enum)]
+    record: Option<Record>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Record {
+    /// Lines written to --output-dir
+    Finding,
+    /// Lines written to --skip-log
+    Skip,
+    /// Lines written to --graph
+    Edge,
+}
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn timestamp() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn group() -> Value {
+    json!({
+        "type": "object",
+        "required": [
+            "id", "name", "description", "owner", "shout", "memberCount",
+            "isBuildersClubOnly", "publicEntryAllowed", "isLocked", "hasVerifiedBadge"
+        ],
+        "properties": {
+            "id": { "type": "integer", "minimum": 0 },
+            "name": { "type": "string" },
+            "description": { "type": "string" },
+            "owner": nullable(json!({
+                "type": "object",
+                "properties": {
+                    "hasVerifiedBadge": { "type": "boolean" },
+                    "userId": { "type": "integer" },
+                    "username": { "type": "string" },
+                    "displayName": { "type": "string" }
+                }
+            })),
+            "shout": nullable(json!({
+                "type": "object",
+                "properties": {
+                    "body": { "type": "string" },
+                    "created": timestamp(),
+                    "updated": timestamp()
+                }
+            })),
+            "memberCount": { "type": "integer", "minimum": 0 },
+            "isBuildersClubOnly": { "type": "boolean" },
+            "publicEntryAllowed": { "type": "boolean" },
+            "isLocked": nullable(json!({ "type": "boolean" })),
+            "hasVerifiedBadge": { "type": "boolean" },
+            "previousName": {
+                "type": "string",
+                "description": "Only known for groups surfaced through search"
+            },
+            "enrichment": {
+                "type": "object",
+                "description": "Fields returned by --enrich-url"
+            },
+            "created": timestamp(),
+            "updated": timestamp()
+        }
+    })
+}
+
+fn finding() -> Value {
+    json!({
+        "title": "Finding",
+        "description": "An available group, one per line in the --output-dir run files \
+            and the body sent to --enrich-url",
+        "type": "object",
+        "required": ["schemaVersion", "group", "score", "found"],
+        "properties": {
+            "schemaVersion": {
+                "const": SCHEMA_VERSION,
+                "description": "Bumped when a field is renamed, removed or changes meaning, \
+                    fields may be added without a bump"
+            },
+            "group": group(),
+            "score": { "type": "integer", "minimum": 0, "maximum": 100 },
+            "socialLinks": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "type", "url", "title"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "type": { "type": "string" },
+                        "url": { "type": "string" },
+                        "title": { "type": "string" }
+                    }
+                }
+            },
+            "query": nullable(json!({ "type": "string" })),
+            "found": timestamp()
+        }
+    })
+}
+
+fn skip() -> Value {
+    json!({
+        "title": "Skip",
+        "description": "A checked group that wasn't reported, one per line in --skip-log",
+        "type": "object",
+        "required": ["id", "name", "reason"],
+        "properties": {
+            "id": { "type": "integer", "minimum": 0 },
+            "name": { "type": "string" },
+            "reason": {
+                "enum": [
+                    "already-checked", "already-reported", "denylisted", "not-allowlisted",
+                    "owned", "locked", "moderated", "closed", "created-date", "updated-date",
+                    "previous-name", "enrichment"
+                ]
+            }
+        }
+    })
+}
+
+fn edge() -> Value {
+    let node = json!({
+        "type": "object",
+        "required": ["id", "name", "owned", "memberCount"],
+        "properties": {
+            "id": { "type": "integer", "minimum": 0 },
+            "name": { "type": "string" },
+            "owned": { "type": "boolean" },
+            "memberCount": { "type": "integer", "minimum": 0 }
+        }
+    });
+
+    json!({
+        "title": "Edge",
+        "description": "A relationship seen while crawling, one per line in --graph",
+        "type": "object",
+        "required": ["group", "related", "relationship"],
+        "properties": {
+            "group": node,
+            "related": node,
+            "relationship": { "enum": ["allies", "enemies"] }
+        }
+    })
+}
+
+/// Prints the JSON Schema of one record, or of every record under `$defs`.
+pub fn schema(schema_args: &SchemaArgs) {
+    let mut schema = match schema_args.record {
+        Some(Record::Finding) => finding(),
+        Some(Record::Skip) => skip(),
+        Some(Record::Edge) => edge(),
+        None => json!({
+            "title": "rbx-reclaimer records",
+            "$defs": {
+                "finding": finding(),
+                "skip": skip(),
+                "edge": edge()
+            }
+        }),
+    };
+
+    schema["$schema"] = json!(DRAFT);
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema).expect("schemas always serialize")
+    );
+}
@@ -0,0 +1,51 @@
+use std::time::{Duration, SystemTime};
+
+use crate::{Group, SocialLink};
+
+/// Names at most this long are rare enough to be valuable on their own.
+const SHORT_NAME_LENGTH: usize = 3;
+
+const YEAR: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Rates how valuable an available group is on a scale from 0 to 100.
+pub fn score_group(group: &Group, social_links: &[SocialLink]) -> u32 {
+    let mut score = 0.;
+
+    score += (f64::from(group.member_count) + 1.).log10() * 10.;
+    score = f64::min(score, 40.);
+
+    if group.public_entry_allowed {
+        score += 15.;
+    }
+
+    score += f64::min(social_links.len() as f64 * 5., 15.);
+
+    if group.has_verified_badge {
+        score += 10.;
+    }
+
+    if !group.description.trim().is_empty() {
+        score += 5.;
+    }
+
+    if group.name.chars().count() <= SHORT_NAME_LENGTH {
+        score += 15.;
+    }
+
+    // Up to 10 more for groups whose sampled members are mostly new accounts.
+    if let Some(activity) = &group.activity {
+        score += activity.estimate() * 10.;
+    }
+
+    // Older groups are worth up to 10 more, a point per year.
+    if let Some(age) = group
+        .created
+        .as_deref()
+        .and_then(|created| humantime::parse_rfc3339_weak(created).ok())
+        .and_then(|created| SystemTime::now().duration_since(created).ok())
+    {
+        score += f64::min(age.as_secs_f64() / YEAR.as_secs_f64(), 10.);
+    }
+
+    f64::min(score, 100.).round() as u32
+}
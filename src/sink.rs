@@ -0,0 +1,338 @@
+use clap::ValueEnum;
+use colored::Colorize;
+use reqwest::Client;
+use serde_json::json;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::{httplog, logfile, output};
+
+/// How long a single webhook delivery may take.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Backoff after the first failed delivery, doubled on every failure after.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// What to do with a message when the delivery queue is full.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait for the webhook to catch up, pausing the scan
+    Block,
+    /// Discard the oldest queued message
+    DropOldest,
+    /// Append the message to the outbox file, delivered once the queue drains
+    Spill,
+}
+
+#[derive(Debug)]
+struct Queued {
+    content: String,
+    enqueued: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    queue: Mutex<VecDeque<Queued>>,
+    /// Woken when a message is queued or the sink is closed.
+    wake: Notify,
+    /// Woken when a message leaves the queue.
+    space: Notify,
+    closed: AtomicBool,
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+    spilled: AtomicU64,
+    /// Held for every read and write of the outbox, the scan appends to it
+    /// while the worker takes messages off the front.
+    outbox: Mutex<()>,
+}
+
+/// Delivers webhook messages from a bounded queue in the background, so a
+/// slow or unreachable webhook doesn't stall scanning.
+#[derive(Debug)]
+pub struct Sink {
+    shared: Arc<Shared>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    outbox: PathBuf,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Sink {
+    pub fn new(
+        webhook: String,
+        client: Client,
+        capacity: usize,
+        policy: OverflowPolicy,
+        outbox: PathBuf,
+    ) -> Self {
+        let shared = Arc::new(Shared::default());
+        let capacity = capacity.max(1);
+
+        let worker = tokio::spawn(deliver(
+            shared.clone(),
+            webhook,
+            client,
+            capacity,
+            outbox.clone(),
+        ));
+
+        Self {
+            shared,
+            capacity,
+            policy,
+            outbox,
+            worker: Some(worker),
+        }
+    }
+
+    /// Queues a message, applying the overflow policy if the queue is full.
+    pub async fn push(&self, content: String) -> std::io::Result<()> {
+        loop {
+            let space = self.shared.space.notified();
+
+            {
+                let mut queue = self.shared.queue.lock().unwrap();
+
+                if queue.len() < self.capacity {
+                    queue.push_back(Queued {
+                        content,
+                        enqueued: Instant::now(),
+                    });
+                    self.shared.wake.notify_one();
+                    return Ok(());
+                }
+
+                match self.policy {
+                    OverflowPolicy::Block => {}
+                    OverflowPolicy::DropOldest => {
+                        queue.pop_front();
+                        queue.push_back(Queued {
+                            content,
+                            enqueued: Instant::now(),
+                        });
+                        self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                    OverflowPolicy::Spill => {
+                        spill(&self.shared, &self.outbox, &content)?;
+                        self.shared.spilled.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                }
+            }
+
+            space.await;
+        }
+    }
+
+    /// How many messages are waiting and how long the oldest has waited.
+    pub fn backlog(&self) -> (usize, Duration) {
+        let queue = self.shared.queue.lock().unwrap();
+
+        (
+            queue.len(),
+            queue
+                .front()
+                .map(|queued| queued.enqueued.elapsed())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Waits for every queued message to be delivered, then reports what
+    /// happened to the messages sent this run.
+    pub async fn close(&mut self) {
+        let Some(worker) = self.worker.take() else {
+            return;
+        };
+
+        self.shared.closed.store(true, Ordering::Relaxed);
+        self.shared.wake.notify_one();
+        worker.await.ok();
+
+        let dropped = self.shared.dropped.load(Ordering::Relaxed);
+        let spilled = self.shared.spilled.load(Ordering::Relaxed);
+        let message = format!(
+            "Webhook: {} delivered, {} failed, {} dropped, {} spilled",
+            self.shared.delivered.load(Ordering::Relaxed),
+            self.shared.failed.load(Ordering::Relaxed),
+            dropped,
+            spilled
+        );
+
+        if dropped > 0 || spilled > 0 {
+            output::info(message.yellow());
+        }
+
+        logfile::log("INFO", &message);
+
+        let undelivered = fs::read_to_string(&self.outbox)
+            .map(|contents| contents.lines().filter(|line| !line.is_empty()).count())
+            .unwrap_or(0);
+
+        if undelivered > 0 {
+            let message = format!(
+                "{} notification{} left in {}, delivery is retried on the next run",
+                undelivered,
+                if undelivered == 1 { "" } else { "s" },
+                self.outbox.display()
+            );
+
+            output::info(message.yellow());
+            logfile::log("WARN", &message);
+        }
+    }
+}
+
+/// Delivers queued messages, then ones left in the outbox. Failed messages
+/// are moved to the outbox and retried once the backoff has passed.
+async fn deliver(
+    shared: Arc<Shared>,
+    webhook: String,
+    client: Client,
+    capacity: usize,
+    outbox: PathBuf,
+) {
+    let mut backoff: Option<Duration> = None;
+
+    loop {
+        let wake = shared.wake.notified();
+        let next = shared.queue.lock().unwrap().pop_front();
+        let closed = shared.closed.load(Ordering::Relaxed);
+
+        let Some(queued) = next else {
+            // Retrying the outbox while closing could loop forever against a
+            // webhook that's down, it's picked up again on the next run.
+            if backoff.is_none() || !closed {
+                match take_spilled(&shared, &outbox, capacity) {
+                    Ok(spilled) if !spilled.is_empty() => {
+                        shared
+                            .queue
+                            .lock()
+                            .unwrap()
+                            .extend(spilled.into_iter().map(|content| Queued {
+                                content,
+                                enqueued: Instant::now(),
+                            }));
+                        continue;
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        logfile::log("ERROR", &format!("Failed to read the outbox: {}", err));
+                    }
+                }
+            }
+
+            if closed {
+                return;
+            }
+
+            wake.await;
+            continue;
+        };
+
+        shared.space.notify_one();
+
+        let result = httplog::send(
+            client
+                .post(&webhook)
+                .timeout(DELIVERY_TIMEOUT)
+                .json(&json!({ "content": queued.content })),
+        )
+        .await
+        .and_then(|response| response.error_for_status());
+
+        let Err(err) = result else {
+            shared.delivered.fetch_add(1, Ordering::Relaxed);
+            backoff = None;
+            continue;
+        };
+
+        shared.failed.fetch_add(1, Ordering::Relaxed);
+
+        let delay = backoff.map_or(INITIAL_BACKOFF, |backoff| (backoff * 2).min(MAX_BACKOFF));
+        backoff = Some(delay);
+
+        let message = match spill(&shared, &outbox, &queued.content) {
+            Ok(()) => format!(
+                "Failed to send notification, retrying from {} in {}: {}",
+                outbox.display(),
+                humantime::format_duration(delay),
+                err
+            ),
+            Err(spill_err) => format!(
+                "Failed to send notification: {} (and to save it to {}: {})",
+                err,
+                outbox.display(),
+                spill_err
+            ),
+        };
+
+        println!("{}", message.red());
+        logfile::log("ERROR", &message);
+
+        if !closed {
+            // Messages still in the queue have to wait out the backoff too,
+            // the webhook is most likely still unreachable.
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = wait_closed(&shared) => {}
+            }
+        }
+    }
+}
+
+/// Resolves once the sink is closed, so a backoff doesn't delay shutdown.
+async fn wait_closed(shared: &Shared) {
+    while !shared.closed.load(Ordering::Relaxed) {
+        shared.wake.notified().await;
+    }
+}
+
+/// Appends a message to the outbox, one JSON string per line.
+fn spill(shared: &Shared, outbox: &Path, content: &str) -> std::io::Result<()> {
+    let _outbox = shared.outbox.lock().unwrap();
+
+    let mut file = OpenOptions::new().create(true).append(true).open(outbox)?;
+    writeln!(file, "{}", serde_json::to_string(content)?)
+}
+
+/// Takes up to `max` messages off the front of the outbox.
+fn take_spilled(shared: &Shared, outbox: &Path, max: usize) -> std::io::Result<Vec<String>> {
+    let _outbox = shared.outbox.lock().unwrap();
+
+    let contents = match fs::read_to_string(outbox) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+
+    let lines: Vec<&str> = contents.lines().filter(|line| !line.is_empty()).collect();
+
+    if lines.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let split = max.min(lines.len());
+    let remaining = &lines[split..];
+
+    if remaining.is_empty() {
+        fs::remove_file(outbox)?;
+    } else {
+        fs::write(outbox, remaining.join("\n") + "\n")?;
+    }
+
+    Ok(lines[..split]
+        .iter()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
@@ -0,0 +1,128 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::store::Category;
+
+/// Why a checked group wasn't reported.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// Listed in the store by an earlier run
+    AlreadyChecked,
+    AlreadyReported,
+    Denylisted,
+    NotAllowlisted,
+    Owned,
+    Locked,
+    Moderated,
+    Closed,
+    CreatedDate,
+    UpdatedDate,
+    PreviousName,
+    Enrichment,
+    Tag,
+    Language,
+}
+
+impl SkipReason {
+    /// The store category of a group skipped after it was checked, `None`
+    /// for skips that happen before a group is added to the store.
+    pub fn category(self) -> Option<Category> {
+        match self {
+            SkipReason::AlreadyChecked
+            | SkipReason::AlreadyReported
+            | SkipReason::Denylisted
+            | SkipReason::NotAllowlisted => None,
+            SkipReason::Owned => Some(Category::Owned),
+            SkipReason::Locked => Some(Category::Locked),
+            SkipReason::Moderated
+            | SkipReason::Closed
+            | SkipReason::CreatedDate
+            | SkipReason::UpdatedDate
+            | SkipReason::PreviousName
+            | SkipReason::Enrichment
+            | SkipReason::Tag
+            | SkipReason::Language => Some(Category::Filtered),
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            SkipReason::AlreadyChecked => "already checked",
+            SkipReason::AlreadyReported => "already reported",
+            SkipReason::Denylisted => "denylisted",
+            SkipReason::NotAllowlisted => "not allowlisted",
+            SkipReason::Owned => "owned",
+            SkipReason::Locked => "locked",
+            SkipReason::Moderated => "moderated",
+            SkipReason::Closed => "closed",
+            SkipReason::CreatedDate => "created date filtered",
+            SkipReason::UpdatedDate => "updated date filtered",
+            SkipReason::PreviousName => "previous name filtered",
+            SkipReason::Enrichment => "enrichment filtered",
+            SkipReason::Tag => "tag filtered",
+            SkipReason::Language => "language filtered",
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct SkipRecord<'a> {
+    id: u32,
+    name: &'a str,
+    reason: SkipReason,
+}
+
+/// Counts skipped groups per reason and optionally exports every skip as a
+/// JSON line.
+#[derive(Debug)]
+pub struct Skips {
+    counts: BTreeMap<SkipReason, u64>,
+    export: Option<File>,
+}
+
+impl Skips {
+    pub fn new(export: Option<&Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            counts: BTreeMap::new(),
+            export: export
+                .map(|path| OpenOptions::new().create(true).append(true).open(path))
+                .transpose()?,
+        })
+    }
+
+    pub fn record(&mut self, id: u32, name: &str, reason: SkipReason) {
+        *self.counts.entry(reason).or_default() += 1;
+
+        if let Some(file) = &mut self.export {
+            if let Ok(line) = serde_json::to_string(&SkipRecord { id, name, reason }) {
+                writeln!(file, "{}", line).ok();
+            }
+        }
+    }
+
+    /// e.g. `Skipped 12: 8 owned, 3 locked, 1 closed`, most common first.
+    pub fn summary(&self) -> Option<String> {
+        let total: u64 = self.counts.values().sum();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut counts: Vec<_> = self.counts.iter().collect();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+
+        let counts: Vec<String> = counts
+            .iter()
+            .map(|(reason, count)| format!("{} {}", count, reason))
+            .collect();
+
+        Some(format!("Skipped {}: {}", total, counts.join(", ")))
+    }
+}
@@ -0,0 +1,64 @@
+use std::net::UdpSocket;
+use std::time::Duration;
+
+/// Emits counters and timers over UDP in the (Dog)StatsD line format. Does
+/// nothing when no address is configured.
+#[derive(Debug)]
+pub struct Statsd {
+    socket: Option<UdpSocket>,
+    prefix: String,
+    tags: Vec<String>,
+}
+
+impl Statsd {
+    pub fn new(addr: Option<&str>, prefix: &str, tags: &[String]) -> std::io::Result<Self> {
+        let socket = match addr {
+            Some(addr) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(addr)?;
+                socket.set_nonblocking(true)?;
+                Some(socket)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            socket,
+            prefix: prefix.trim_end_matches('.').to_string(),
+            tags: tags.to_vec(),
+        })
+    }
+
+    pub fn count(&self, metric: &str, value: u64) {
+        self.send(metric, value.to_string().as_str(), "c");
+    }
+
+    pub fn increment(&self, metric: &str) {
+        self.count(metric, 1);
+    }
+
+    pub fn timing(&self, metric: &str, duration: Duration) {
+        self.send(
+            metric,
+            format!("{:.3}", duration.as_secs_f64() * 1000.).as_str(),
+            "ms",
+        );
+    }
+
+    fn send(&self, metric: &str, value: &str, kind: &str) {
+        let Some(socket) = &self.socket else {
+            return;
+        };
+
+        let mut line = format!("{}.{}:{}|{}", self.prefix, metric, value, kind);
+
+        if !self.tags.is_empty() {
+            line.push_str("|#");
+            line.push_str(&self.tags.join(","));
+        }
+
+        // Metrics are best effort, a full buffer or unreachable agent must
+        // never slow down scanning.
+        socket.send(line.as_bytes()).ok();
+    }
+}
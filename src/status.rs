@@ -0,0 +1,141 @@
+use colored::Colorize;
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crate::api::ApiStats;
+
+/// How often the status line is redrawn.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The window the current request rate is averaged over.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// How throughput is reported while scanning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusMode {
+    Off,
+    /// A line on stderr redrawn in place, only on terminals.
+    Live,
+    /// A plain line on stdout printed every interval, for terminals that
+    /// can't redraw and for logs.
+    Periodic(Duration),
+}
+
+/// A single line showing live throughput.
+#[derive(Debug)]
+pub struct StatusLine {
+    mode: StatusMode,
+    started: Instant,
+    last_render: Option<Instant>,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl StatusLine {
+    pub fn new(mode: StatusMode) -> Self {
+        let started = Instant::now();
+
+        Self {
+            mode: match mode {
+                StatusMode::Live if !std::io::stderr().is_terminal() => StatusMode::Off,
+                mode => mode,
+            },
+            started,
+            last_render: None,
+            samples: VecDeque::from([(started, 0)]),
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        stats: ApiStats,
+        checked: u64,
+        found: usize,
+        backlog: Option<(usize, Duration)>,
+    ) {
+        let (interval, window) = match self.mode {
+            StatusMode::Off => return,
+            StatusMode::Live => (REFRESH_INTERVAL, RATE_WINDOW),
+            // The rate has to span at least the previous line.
+            StatusMode::Periodic(interval) => (interval, RATE_WINDOW.max(interval * 2)),
+        };
+
+        let now = Instant::now();
+
+        match self.last_render {
+            Some(last_render) if now.duration_since(last_render) < interval => return,
+            // The first periodic line waits for a full interval of throughput.
+            None if matches!(self.mode, StatusMode::Periodic(_))
+                && now.duration_since(self.started) < interval =>
+            {
+                return
+            }
+            _ => {}
+        }
+
+        self.last_render = Some(now);
+        self.samples.push_back((now, stats.requests));
+
+        while self
+            .samples
+            .front()
+            .is_some_and(|(instant, _)| now.duration_since(*instant) > window)
+        {
+            self.samples.pop_front();
+        }
+
+        let requests_per_second = match self.samples.front() {
+            Some((instant, requests)) if now > *instant => {
+                (stats.requests - requests) as f64 / now.duration_since(*instant).as_secs_f64()
+            }
+            _ => 0.,
+        };
+
+        let minutes = self.started.elapsed().as_secs_f64() / 60.;
+
+        let mut line = format!(
+            "{:.1} req/s │ {:.1}% ok │ {:.1} groups/min │ {} checked │ {} found",
+            requests_per_second,
+            (1. - stats.error_rate()) * 100.,
+            if minutes > 0. {
+                checked as f64 / minutes
+            } else {
+                0.
+            },
+            checked,
+            found
+        );
+
+        if let Some((queued, lag)) = backlog.filter(|(queued, _)| *queued > 0) {
+            line.push_str(&format!(
+                " │ {} queued ({:.0}s behind)",
+                queued,
+                lag.as_secs_f64()
+            ));
+        }
+
+        if let StatusMode::Periodic(_) = self.mode {
+            println!(
+                "{} │ {} errors │ {} elapsed",
+                line,
+                stats.errors,
+                humantime::format_duration(Duration::from_secs(self.started.elapsed().as_secs()))
+            );
+            return;
+        }
+
+        eprint!("\r\x1b[2K{}", line.truecolor(140, 140, 140));
+        std::io::stderr().flush().ok();
+    }
+
+    /// Erases the status line so regular output can be printed in its place.
+    pub fn clear(&mut self) {
+        if self.mode != StatusMode::Live || self.last_render.is_none() {
+            return;
+        }
+
+        eprint!("\r\x1b[2K");
+        std::io::stderr().flush().ok();
+        self.last_render = None;
+    }
+}
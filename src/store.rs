@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+/// Why a group id is in the store.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum Category {
+    /// Being checked, or checked by a version that didn't record the outcome.
+    Checked,
+    /// Reported as available.
+    FoundAvailable,
+    Owned,
+    Locked,
+    /// Deleted or never existed.
+    Nonexistent,
+    /// Unowned but left out by a filter, such as being closed or moderated.
+    Filtered,
+    /// Claimed by the account running the scan.
+    ClaimedByMe,
+    /// Claimed by someone else before the account running the scan could.
+    ClaimLost,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Category::Checked => "checked",
+            Category::FoundAvailable => "found available",
+            Category::Owned => "owned",
+            Category::Locked => "locked",
+            Category::Nonexistent => "nonexistent",
+            Category::Filtered => "filtered",
+            Category::ClaimedByMe => "claimed by me",
+            Category::ClaimLost => "claim lost",
+        })
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "checked" => Ok(Category::Checked),
+            "found-available" => Ok(Category::FoundAvailable),
+            "owned" => Ok(Category::Owned),
+            "locked" => Ok(Category::Locked),
+            "nonexistent" => Ok(Category::Nonexistent),
+            "filtered" => Ok(Category::Filtered),
+            "claimed-by-me" => Ok(Category::ClaimedByMe),
+            "claim-lost" => Ok(Category::ClaimLost),
+            _ => Err(format!("unknown category {value}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Entry {
+    pub id: u32,
+    pub category: Category,
+    /// When the group was last put in its category, unknown for entries
+    /// written before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+}
+
+impl Entry {
+    /// How long ago the group was put in its category, if that's known.
+    pub fn age(&self) -> Option<Duration> {
+        let at = humantime::parse_rfc3339(self.at.as_deref()?).ok()?;
+
+        SystemTime::now().duration_since(at).ok()
+    }
+}
+
+/// Stores written before categories existed are plain arrays of ids.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredEntry {
+    Legacy(u32),
+    Entry(Entry),
+}
+
+impl From<StoredEntry> for Entry {
+    fn from(entry: StoredEntry) -> Self {
+        match entry {
+            StoredEntry::Legacy(id) => Entry {
+                id,
+                category: Category::Checked,
+                at: None,
+            },
+            StoredEntry::Entry(entry) => entry,
+        }
+    }
+}
+
+pub fn load(store: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    if !store.exists() {
+        return Ok(vec![]);
+    }
+
+    let entries: Vec<StoredEntry> = serde_json::from_str(&fs::read_to_string(store)?)?;
+
+    Ok(entries.into_iter().map(Entry::from).collect())
+}
+
+/// Whether the group is in the store, and with `fresh_within`, was put there
+/// within that long. Entries of unknown age are never fresh.
+pub fn is_fresh(
+    store: &Path,
+    group_id: u32,
+    fresh_within: Option<Duration>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(load(store)?.iter().any(|entry| {
+        entry.id == group_id
+            && fresh_within.is_none_or(|within| entry.age().is_some_and(|age| age <= within))
+    }))
+}
+
+/// Adds a group to the store, or moves it to `category` if it's already in it.
+pub fn exclude(
+    store: &Path,
+    group_id: u32,
+    category: Category,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut entries = load(store)?;
+    entries.retain(|entry| entry.id != group_id);
+    entries.push(Entry {
+        id: group_id,
+        category,
+        at: Some(humantime::format_rfc3339_seconds(SystemTime::now()).to_string()),
+    });
+
+    fs::write(store, serde_json::to_string(&entries)?)?;
+
+    Ok(())
+}
+
+/// Drops a group from the store so it can be checked again.
+pub fn remove(store: &Path, group_id: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let mut entries = load(store)?;
+    entries.retain(|entry| entry.id != group_id);
+
+    fs::write(store, serde_json::to_string(&entries)?)?;
+
+    Ok(())
+}
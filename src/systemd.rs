@@ -0,0 +1,151 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often the service status line is refreshed.
+const STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Set by [`Systemd::from_env`] when systemd expects keep-alives, so they can
+/// be sent from inside the waits deep in the api as well as between groups.
+static WATCHDOG: Mutex<Option<Watchdog>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Watchdog {
+    socket: String,
+    interval: Duration,
+    last: Instant,
+}
+
+/// Reports readiness, watchdog keep-alives and progress to systemd through
+/// `$NOTIFY_SOCKET`. Does nothing when not run as a notify service.
+#[derive(Debug)]
+pub struct Systemd {
+    socket: Option<String>,
+    last_status: Option<Instant>,
+}
+
+impl Systemd {
+    pub fn from_env() -> Self {
+        let socket = std::env::var("NOTIFY_SOCKET").ok();
+
+        // systemd expects keep-alives at least twice per watchdog interval.
+        let watchdog_interval = std::env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|usec| usec.parse::<u64>().ok())
+            .filter(|_| {
+                std::env::var("WATCHDOG_PID")
+                    .ok()
+                    .and_then(|pid| pid.parse::<u32>().ok())
+                    .is_none_or(|pid| pid == std::process::id())
+            })
+            .map(|usec| Duration::from_micros(usec) / 2);
+
+        if let (Some(socket), Some(interval)) = (&socket, watchdog_interval) {
+            *WATCHDOG.lock().unwrap() = Some(Watchdog {
+                socket: socket.clone(),
+                interval,
+                last: Instant::now(),
+            });
+        }
+
+        Self {
+            socket,
+            last_status: None,
+        }
+    }
+
+    pub fn ready(&self) {
+        self.notify("READY=1");
+    }
+
+    pub fn stopping(&self) {
+        self.notify("STOPPING=1");
+    }
+
+    /// Sends a watchdog keep-alive and refreshes the status line when due.
+    pub fn tick(&mut self, checked: u64, found: usize) {
+        keep_alive();
+
+        if self.socket.is_none() {
+            return;
+        }
+
+        if self
+            .last_status
+            .is_none_or(|last_status| last_status.elapsed() >= STATUS_INTERVAL)
+        {
+            self.notify(&format!(
+                "STATUS=Checked {} groups, found {}",
+                checked, found
+            ));
+            self.last_status = Some(Instant::now());
+        }
+    }
+
+    fn notify(&self, state: &str) {
+        if let Some(socket) = &self.socket {
+            notify(socket, state);
+        }
+    }
+}
+
+/// Sends a watchdog keep-alive if one is due.
+pub fn keep_alive() {
+    if let Some(watchdog) = WATCHDOG.lock().unwrap().as_mut() {
+        if watchdog.last.elapsed() >= watchdog.interval {
+            notify(&watchdog.socket, "WATCHDOG=1");
+            watchdog.last = Instant::now();
+        }
+    }
+}
+
+/// Sleeps for `duration`, sending watchdog keep-alives along the way so a
+/// long rate limit or breaker wait isn't taken for a hung scan.
+pub async fn sleep(duration: Duration) {
+    let until = Instant::now() + duration;
+    let step = WATCHDOG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map_or(duration, |watchdog| watchdog.interval);
+
+    loop {
+        keep_alive();
+
+        let remaining = until.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            break;
+        }
+
+        tokio::time::sleep(remaining.min(step)).await;
+    }
+}
+
+#[cfg(unix)]
+fn notify(socket: &str, state: &str) {
+    use std::os::unix::net::UnixDatagram;
+
+    let Ok(datagram) = UnixDatagram::unbound() else {
+        return;
+    };
+
+    if let Some(name) = socket.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::linux::net::SocketAddrExt;
+            use std::os::unix::net::SocketAddr;
+
+            if let Ok(addr) = SocketAddr::from_abstract_name(name.as_bytes()) {
+                datagram.send_to_addr(state.as_bytes(), &addr).ok();
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = name;
+    } else {
+        datagram.send_to(state.as_bytes(), socket).ok();
+    }
+}
+
+#[cfg(not(unix))]
+fn notify(_socket: &str, _state: &str) {}
@@ -0,0 +1,143 @@
+use regex::Regex;
+
+use crate::Group;
+
+/// Keywords that mark a group as one kind or another, matched as whole words
+/// in its name and description. `--tag-keyword` adds to these.
+const BUILT_IN: &[(&str, &[&str])] = &[
+    (
+        "clan",
+        &[
+            "clan",
+            "army",
+            "military",
+            "regiment",
+            "battalion",
+            "division",
+            "legion",
+            "corps",
+            "troops",
+            "squad",
+            "faction",
+            "empire",
+            "militia",
+        ],
+    ),
+    (
+        "fan",
+        &[
+            "fan",
+            "fans",
+            "fan club",
+            "fanclub",
+            "fandom",
+            "stans",
+            "supporters",
+        ],
+    ),
+    (
+        "giveaway",
+        &[
+            "giveaway",
+            "giveaways",
+            "free robux",
+            "robux giveaway",
+            "free items",
+            "payout",
+            "payouts",
+            "donations",
+        ],
+    ),
+    (
+        "trading",
+        &[
+            "trade",
+            "trades",
+            "trading",
+            "traders",
+            "limiteds",
+            "rap",
+            "values",
+            "ugc trading",
+        ],
+    ),
+    (
+        "clothing",
+        &[
+            "clothing", "clothes", "shirts", "pants", "outfits", "apparel", "fashion", "designs",
+            "boutique", "merch",
+        ],
+    ),
+];
+
+/// Classifies groups by keywords in their name and description.
+#[derive(Debug)]
+pub struct Tagger {
+    tags: Vec<(String, Regex)>,
+}
+
+impl Tagger {
+    /// Builds the built-in tags along with `extra` `(tag, keyword)` pairs,
+    /// which either add keywords to a built-in tag or make up a new one.
+    pub fn new(extra: &[(String, String)]) -> Result<Self, regex::Error> {
+        let mut keywords: Vec<(String, Vec<String>)> = BUILT_IN
+            .iter()
+            .map(|(tag, words)| {
+                (
+                    tag.to_string(),
+                    words.iter().map(|word| word.to_string()).collect(),
+                )
+            })
+            .collect();
+
+        for (tag, keyword) in extra {
+            match keywords.iter_mut().find(|(existing, _)| existing == tag) {
+                Some((_, words)) => words.push(keyword.clone()),
+                None => keywords.push((tag.clone(), vec![keyword.clone()])),
+            }
+        }
+
+        let tags = keywords
+            .into_iter()
+            .map(|(tag, words)| {
+                let alternatives = words
+                    .iter()
+                    .map(|word| regex::escape(word))
+                    .collect::<Vec<String>>()
+                    .join("|");
+
+                Ok((tag, Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives))?))
+            })
+            .collect::<Result<_, regex::Error>>()?;
+
+        Ok(Self { tags })
+    }
+
+    /// Every tag whose keywords appear in the group's name or description.
+    pub fn tag(&self, group: &Group) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter(|(_, pattern)| {
+                pattern.is_match(&group.name) || pattern.is_match(&group.description)
+            })
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+}
+
+/// Parses a `--tag-keyword` as `tag=keyword`.
+pub fn parse_keyword(value: &str) -> Result<(String, String), String> {
+    let (tag, keyword) = value
+        .split_once('=')
+        .ok_or_else(|| String::from("tag keyword must be formatted as 'tag=keyword'"))?;
+
+    let (tag, keyword) = (tag.trim().to_lowercase(), keyword.trim().to_lowercase());
+
+    if tag.is_empty() || keyword.is_empty() {
+        return Err(String::from(
+            "tag keyword must be formatted as 'tag=keyword'",
+        ));
+    }
+
+    Ok((tag, keyword))
+}
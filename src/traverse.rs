@@ -0,0 +1,104 @@
+use crate::Group;
+
+/// A condition related groups have to meet for the relationship crawler to
+/// descend into them, negated with a leading `!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraverseFilter {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Condition {
+    /// `members>N`, `members>=N`, `members<N`, `members<=N` or `members=N`
+    Members(Comparison, u32),
+    /// `unowned`
+    Unowned,
+    /// `open`
+    Open,
+    /// `verified`
+    Verified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn compare(self, value: u32, expected: u32) -> bool {
+        match self {
+            Comparison::Greater => value > expected,
+            Comparison::GreaterOrEqual => value >= expected,
+            Comparison::Less => value < expected,
+            Comparison::LessOrEqual => value <= expected,
+            Comparison::Equal => value == expected,
+        }
+    }
+}
+
+/// Parses a single traversal filter, e.g. `members>10` or `!verified`.
+pub fn parse_filter(value: &str) -> Result<TraverseFilter, String> {
+    let value = value.trim();
+    let (negated, condition) = match value.strip_prefix('!') {
+        Some(condition) => (true, parse_condition(condition.trim())?),
+        None => (false, parse_condition(value)?),
+    };
+
+    Ok(TraverseFilter { negated, condition })
+}
+
+fn parse_condition(value: &str) -> Result<Condition, String> {
+    match value.to_lowercase().as_str() {
+        "unowned" => return Ok(Condition::Unowned),
+        "open" => return Ok(Condition::Open),
+        "verified" => return Ok(Condition::Verified),
+        _ => {}
+    }
+
+    let Some(count) = value.strip_prefix("members") else {
+        return Err(format!(
+            "unknown traversal filter {value}, expected members<op>N, unowned, open or verified"
+        ));
+    };
+
+    let (comparison, count) = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(operator, comparison)| {
+        count
+            .trim_start()
+            .strip_prefix(operator)
+            .map(|count| (comparison, count))
+    })
+    .ok_or_else(|| format!("expected >, >=, <, <= or = after members in {value}"))?;
+
+    let count = count
+        .trim()
+        .parse::<u32>()
+        .map_err(|err| format!("invalid member count in {value}: {err}"))?;
+
+    Ok(Condition::Members(comparison, count))
+}
+
+impl TraverseFilter {
+    pub fn matches(&self, group: &Group) -> bool {
+        let matches = match self.condition {
+            Condition::Members(comparison, count) => comparison.compare(group.member_count, count),
+            Condition::Unowned => group.owner.is_none(),
+            Condition::Open => group.public_entry_allowed,
+            Condition::Verified => group.has_verified_badge,
+        };
+
+        matches != self.negated
+    }
+}
@@ -0,0 +1,198 @@
+use clap::Args as ClapArgs;
+use colored::Colorize;
+use reqwest::Client;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+use crate::{httplog, sha256};
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct UpdateArgs {
+    /// Only report whether a newer release exists, exiting with 1 if so
+    #[arg(long, env = "RECLAIMER_UPDATE_CHECK_ONLY")]
+    check_only: bool,
+
+    /// GitHub repository releases are fetched from
+    #[arg(
+        long,
+        default_value = "synpixel/rbx-reclaimer",
+        env = "RECLAIMER_UPDATE_REPOSITORY"
+    )]
+    repository: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Parses `v1.2.3` or `1.2.3` into comparable version numbers.
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// The release asset built for this platform, e.g. one named
+/// `rbx-reclaimer-x86_64-linux` or `rbx-reclaimer-x86_64-windows.exe`.
+fn platform_asset(assets: &[Asset]) -> Option<&Asset> {
+    let os: &[&str] = match std::env::consts::OS {
+        "macos" => &["macos", "darwin"],
+        os => &[os],
+    };
+
+    assets.iter().find(|asset| {
+        let name = asset.name.to_lowercase();
+
+        name.contains(std::env::consts::ARCH)
+            && os.iter().any(|os| name.contains(os))
+            && !name.ends_with(".sha256")
+    })
+}
+
+/// The expected checksum of `asset`, from a `<asset>.sha256` file or a
+/// `SHA256SUMS` listing published alongside it.
+async fn expected_checksum(
+    asset: &Asset,
+    assets: &[Asset],
+    client: &Client,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let checksums = assets
+        .iter()
+        .find(|other| other.name == format!("{}.sha256", asset.name))
+        .or_else(|| {
+            assets
+                .iter()
+                .find(|other| other.name.eq_ignore_ascii_case("SHA256SUMS"))
+        })
+        .ok_or("the release has no checksums to verify the download with")?;
+
+    let contents = httplog::send(client.get(&checksums.browser_download_url))
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?, parts.next()))
+        })
+        .find(|(_, name)| name.is_none_or(|name| name.trim_start_matches('*') == asset.name))
+        .map(|(checksum, _)| checksum.to_lowercase())
+        .ok_or_else(|| format!("no checksum listed for {}", asset.name).into())
+}
+
+/// Swaps the running binary for `binary`. The old binary is moved aside first
+/// since Windows doesn't allow overwriting a running executable.
+fn replace_binary(current: &Path, binary: &[u8]) -> std::io::Result<()> {
+    let staged = current.with_extension("new");
+    let previous = current.with_extension("old");
+
+    fs::write(&staged, binary)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    fs::remove_file(&previous).ok();
+    fs::rename(current, &previous)?;
+
+    if let Err(err) = fs::rename(&staged, current) {
+        fs::rename(&previous, current).ok();
+        return Err(err);
+    }
+
+    // Windows keeps the running binary locked, it's cleaned up next update.
+    fs::remove_file(&previous).ok();
+
+    Ok(())
+}
+
+/// Checks the latest GitHub release and, unless `--check-only`, downloads the
+/// binary for this platform, verifies its checksum and replaces this one.
+/// Returns whether a newer release is available but wasn't installed.
+pub async fn update(
+    update_args: &UpdateArgs,
+    client: &Client,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let current = env!("CARGO_PKG_VERSION");
+
+    let release = httplog::send(
+        client
+            .get(format!(
+                "https://api.github.com/repos/{}/releases/latest",
+                update_args.repository
+            ))
+            .header("Accept", "application/vnd.github+json"),
+    )
+    .await?
+    .error_for_status()?
+    .json::<Release>()
+    .await?;
+
+    if parse_version(&release.tag_name) <= parse_version(current) {
+        println!("{}", format!("Up to date ({})", current).green());
+        return Ok(false);
+    }
+
+    println!(
+        "{}",
+        format!("Update available: {} → {}", current, release.tag_name).yellow()
+    );
+
+    if update_args.check_only {
+        return Ok(true);
+    }
+
+    let asset = platform_asset(&release.assets).ok_or_else(|| {
+        format!(
+            "release {} has no binary for {}-{}",
+            release.tag_name,
+            std::env::consts::ARCH,
+            std::env::consts::OS
+        )
+    })?;
+
+    let expected = expected_checksum(asset, &release.assets, client).await?;
+
+    println!("Downloading {}", asset.name);
+
+    let binary = httplog::send(client.get(&asset.browser_download_url))
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    let actual = sha256::hex_digest(&binary);
+
+    if actual != expected {
+        return Err(format!(
+            "checksum mismatch for {}: expected {}, got {}",
+            asset.name, expected, actual
+        )
+        .into());
+    }
+
+    replace_binary(&std::env::current_exe()?, &binary)?;
+
+    println!(
+        "{}",
+        format!("Updated to {}, checksum verified", release.tag_name).green()
+    );
+
+    Ok(false)
+}
@@ -0,0 +1,175 @@
+use colored::Colorize;
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+/// Whether to guide the user through setting up a run: started without
+/// arguments from an interactive terminal and nothing configured yet.
+pub fn should_run() -> bool {
+    std::env::args_os().len() == 1
+        && std::io::stdin().is_terminal()
+        && !std::env::vars_os().any(|(key, _)| key.to_string_lossy().starts_with("RECLAIMER_"))
+}
+
+fn ask(question: &str, default: &str) -> std::io::Result<String> {
+    if default.is_empty() {
+        print!("{} ", question.bold());
+    } else {
+        print!(
+            "{} {} ",
+            question.bold(),
+            format!("({})", default).truecolor(140, 140, 140)
+        );
+    }
+
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+
+    if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+
+    let answer = answer.trim();
+
+    Ok(if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer.to_string()
+    })
+}
+
+fn ask_yes_no(question: &str, default: bool) -> std::io::Result<bool> {
+    loop {
+        let answer = ask(question, if default { "Y/n" } else { "y/N" })?;
+
+        match answer.to_lowercase().as_str() {
+            "y/n" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("{}", "Please answer y or n".red()),
+        }
+    }
+}
+
+/// Asks until the answer passes `validate`.
+fn ask_valid<T>(
+    question: &str,
+    default: &str,
+    validate: impl Fn(&str) -> Result<T, String>,
+) -> std::io::Result<T> {
+    loop {
+        match validate(&ask(question, default)?) {
+            Ok(value) => return Ok(value),
+            Err(err) => println!("{}", err.red()),
+        }
+    }
+}
+
+/// Asks how to scan and where to send findings, then saves the answers to
+/// `env_file` as `RECLAIMER_*` variables so later runs reuse them.
+pub fn run(env_file: &Path) -> std::io::Result<()> {
+    println!("{}", "No options given, let's set up a scan.".bold());
+    println!(
+        "{}",
+        "Answers are saved as RECLAIMER_* settings, press enter to keep the default."
+            .truecolor(140, 140, 140)
+    );
+
+    let mut settings: Vec<(&str, String)> = vec![];
+
+    let by_keyword = ask_valid(
+        "Search by keyword or scan random group ids? [keyword/random]",
+        "random",
+        |answer| match answer.to_lowercase().as_str() {
+            "k" | "keyword" => Ok(true),
+            "r" | "random" => Ok(false),
+            _ => Err(String::from("Please answer keyword or random")),
+        },
+    )?;
+
+    if by_keyword {
+        let queries = ask_valid("Keywords, separated by |", "", |answer| {
+            answer
+                .split('|')
+                .map(crate::parse_query)
+                .collect::<Result<Vec<String>, String>>()
+        })?;
+
+        settings.push(("RECLAIMER_QUERY", queries.join("|")));
+    } else {
+        let parse_id = |answer: &str| {
+            answer
+                .parse::<u32>()
+                .map_err(|_| String::from("Please enter a group id"))
+        };
+
+        let min = ask_valid("Lowest group id to scan", "1", parse_id)?;
+        let max = ask_valid("Highest group id to scan", "17064733", |answer| {
+            parse_id(answer).and_then(|max| {
+                if max >= min {
+                    Ok(max)
+                } else {
+                    Err(format!("Please enter an id of at least {}", min))
+                }
+            })
+        })?;
+
+        settings.push(("RECLAIMER_MIN", min.to_string()));
+        settings.push(("RECLAIMER_MAX", max.to_string()));
+    }
+
+    if ask_yes_no("Skip closed groups?", false)? {
+        settings.push(("RECLAIMER_IGNORE_CLOSED_GROUPS", String::from("true")));
+    }
+
+    if ask_yes_no("Keep searching after the first group is found?", true)? {
+        settings.push(("RECLAIMER_REPEAT", String::from("true")));
+    }
+
+    let webhook = ask(
+        "Discord webhook to notify about findings (blank for none)",
+        "",
+    )?;
+
+    if !webhook.is_empty() {
+        let min_members = ask_valid(
+            "Only notify about groups with at least this many members",
+            "0",
+            |answer| {
+                answer
+                    .parse::<u32>()
+                    .map_err(|_| String::from("Please enter a number"))
+            },
+        )?;
+
+        settings.push(("RECLAIMER_WEBHOOK", webhook));
+        settings.push(("RECLAIMER_NOTIFY_MIN_MEMBERS", min_members.to_string()));
+    }
+
+    let contents: String = settings
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"\n", key, value))
+        .collect();
+
+    if let Some(dir) = env_file.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(env_file, contents)?;
+
+    for (key, value) in settings {
+        std::env::set_var(key, value);
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Saved to {}, starting the scan. Edit or delete it to change these settings.",
+            env_file.display()
+        )
+        .green()
+    );
+
+    Ok(())
+}